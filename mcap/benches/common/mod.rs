@@ -0,0 +1,39 @@
+use mcap::{Surface, Vec3, get_face_normal};
+use modelz::Model3D;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[path = "../../common/gltf.rs"]
+mod gltf;
+
+// the bundled level-like scenes in res/
+pub const SCENES: &[&str] = &["map", "map2", "map3", "nmap", "auto", "auto2"];
+
+pub fn load_scene(name: &str) -> Vec<Surface> {
+    let path = format!("{}/res/{name}.glb", env!("CARGO_MANIFEST_DIR"));
+    gltf::triangles(Model3D::load(&path).unwrap())
+        .into_iter()
+        .map(|[v1, v2, v3]| Surface::new([v1, v2, v3], get_face_normal(v1, v2, v3)))
+        .collect()
+}
+
+// where a character might stand, a little above random floors,
+// seeded so runs compare
+#[allow(dead_code)]
+pub fn standing_points(surfaces: &[Surface], n: usize) -> Vec<Vec3> {
+    let floors: Vec<&Surface> = surfaces
+        .iter()
+        .filter(|s| matches!(s, Surface::Floor(_)))
+        .collect();
+    if floors.is_empty() {
+        return vec![];
+    }
+
+    let mut rng = StdRng::seed_from_u64(50);
+    (0..n)
+        .map(|_| {
+            let [a, b, c] = floors[rng.random_range(0..floors.len())].triangle().verts();
+            (a + b + c) / 3. + Vec3::new(0., 0.1, 0.)
+        })
+        .collect()
+}
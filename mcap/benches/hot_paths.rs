@@ -0,0 +1,149 @@
+// what a character step spends its time in, on the bundled scenes
+// cargo bench -p mcap -- --save-baseline before, then --baseline before
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use mcap::{
+    CharacterController, Surface, SurfaceGrid, Vec3, closest_point_triangle,
+    find_ciel_height_hotdog_v3, find_floor_height_hotdog_v4, push_out_walls_2,
+};
+use std::hint::black_box;
+
+mod common;
+
+const POINTS: usize = 256;
+
+// the player's proportions, the rest are the controller's defaults
+fn character() -> CharacterController {
+    CharacterController::new(Vec3::ZERO, 2.7, 1.)
+}
+
+// each scene's grid and points on it, with what's near each point
+fn scenes() -> Vec<(&'static str, SurfaceGrid, Vec<Vec3>)> {
+    common::SCENES
+        .iter()
+        .map(|scene| {
+            let surfaces = common::load_scene(scene);
+            let points = common::standing_points(&surfaces, POINTS);
+            (*scene, SurfaceGrid::new(surfaces), points)
+        })
+        .collect()
+}
+
+fn near<'a>(grid: &'a SurfaceGrid, points: &[Vec3]) -> Vec<Vec<&'a Surface>> {
+    points.iter().map(|p| grid.surfaces_near(*p)).collect()
+}
+
+fn walls(c: &mut Criterion) {
+    let cc = character();
+    let mut group = c.benchmark_group("push_out_walls_2");
+    for (scene, grid, points) in &scenes() {
+        let near = near(grid, points);
+        group.bench_function(*scene, |b| {
+            b.iter(|| {
+                for (p, surfaces) in points.iter().zip(&near) {
+                    black_box(push_out_walls_2(
+                        black_box(*p),
+                        cc.chest_height(),
+                        cc.radius,
+                        surfaces,
+                    ));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn floors(c: &mut Criterion) {
+    let cc = character();
+    let mut group = c.benchmark_group("find_floor_height_hotdog_v4");
+    for (scene, grid, points) in &scenes() {
+        let near = near(grid, points);
+        group.bench_function(*scene, |b| {
+            b.iter(|| {
+                for (p, surfaces) in points.iter().zip(&near) {
+                    black_box(find_floor_height_hotdog_v4(
+                        black_box(*p),
+                        cc.snap_up,
+                        cc.snap_down,
+                        surfaces,
+                        cc.radius / 2.,
+                    ));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn ceilings(c: &mut Criterion) {
+    let cc = character();
+    let mut group = c.benchmark_group("find_ciel_height_hotdog_v3");
+    for (scene, grid, points) in &scenes() {
+        let near = near(grid, points);
+        group.bench_function(*scene, |b| {
+            b.iter(|| {
+                for (p, surfaces) in points.iter().zip(&near) {
+                    black_box(find_ciel_height_hotdog_v3(
+                        black_box(*p),
+                        cc.chest_height(),
+                        cc.height - cc.chest_height(),
+                        surfaces,
+                        cc.radius / 2.,
+                    ));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn closest_points(c: &mut Criterion) {
+    let cc = character();
+    let mut group = c.benchmark_group("closest_point_triangle");
+    for (scene, grid, points) in &scenes() {
+        let near = near(grid, points);
+        group.bench_function(*scene, |b| {
+            b.iter(|| {
+                for (p, surfaces) in points.iter().zip(&near) {
+                    let chest = *p + Vec3::new(0., cc.chest_height(), 0.);
+                    for s in surfaces {
+                        black_box(closest_point_triangle(
+                            black_box(chest),
+                            &s.triangle().verts(),
+                        ));
+                    }
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn grid(c: &mut Criterion) {
+    let mut group = c.benchmark_group("surface_grid");
+    for (scene, grid, points) in &scenes() {
+        let surfaces: Vec<Surface> = grid
+            .all_surfaces()
+            .unwrap_or_default()
+            .iter()
+            .map(|s| **s)
+            .collect();
+        group.bench_function(BenchmarkId::new("new", scene), |b| {
+            b.iter_batched(|| surfaces.clone(), SurfaceGrid::new, BatchSize::LargeInput)
+        });
+        group.bench_function(
+            BenchmarkId::new("surfaces_in_cell_and_adjacent", scene),
+            |b| {
+                b.iter(|| {
+                    for p in points {
+                        black_box(grid.surfaces_in_cell_and_adjacent(grid.cell_of(black_box(*p))));
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, walls, floors, ceilings, closest_points, grid);
+criterion_main!(benches);
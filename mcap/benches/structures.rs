@@ -0,0 +1,84 @@
+// SurfaceGrid at a few cell sizes against SurfaceBvh, on the bundled scenes
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use mcap::{SurfaceBvh, SurfaceGrid, SurfaceQuery, Vec3};
+use std::hint::black_box;
+
+mod common;
+
+const CELL_SIZES: &[f32] = &[5., 10., 20.];
+
+fn structures(surfaces: &[mcap::Surface]) -> Vec<(String, Box<dyn SurfaceQuery>)> {
+    let mut out: Vec<(String, Box<dyn SurfaceQuery>)> = vec![];
+    for size in CELL_SIZES {
+        let grid = SurfaceGrid::with_cell_size(surfaces.to_vec(), *size);
+        out.push((format!("grid_{size}"), Box::new(grid)));
+    }
+    let bvh = SurfaceBvh::new(surfaces.to_vec());
+    out.push(("bvh".to_string(), Box::new(bvh)));
+    out
+}
+
+fn build(c: &mut Criterion) {
+    let mut group = c.benchmark_group("build");
+    for scene in common::SCENES {
+        let surfaces = common::load_scene(scene);
+        for size in CELL_SIZES {
+            let id = BenchmarkId::new(format!("grid_{size}"), scene);
+            group.bench_function(id, |b| {
+                b.iter(|| SurfaceGrid::with_cell_size(black_box(surfaces.clone()), *size))
+            });
+        }
+        group.bench_function(BenchmarkId::new("bvh", scene), |b| {
+            b.iter(|| SurfaceBvh::new(black_box(surfaces.clone())))
+        });
+    }
+    group.finish();
+}
+
+fn surfaces_near(c: &mut Criterion) {
+    let mut group = c.benchmark_group("surfaces_near");
+    for scene in common::SCENES {
+        let surfaces = common::load_scene(scene);
+        let points = common::standing_points(&surfaces, 256);
+        for (name, query) in structures(&surfaces) {
+            group.bench_function(BenchmarkId::new(name, scene), |b| {
+                b.iter(|| {
+                    let mut found = 0;
+                    for p in &points {
+                        found += query.surfaces_near(black_box(*p)).len();
+                    }
+                    found
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+fn casts(c: &mut Criterion) {
+    let mut group = c.benchmark_group("casts");
+    for scene in common::SCENES {
+        let surfaces = common::load_scene(scene);
+        let points = common::standing_points(&surfaces, 256);
+        for (name, query) in structures(&surfaces) {
+            // what a character does each tick, a sweep and a look down
+            group.bench_function(BenchmarkId::new(name, scene), |b| {
+                b.iter(|| {
+                    let mut hits = 0;
+                    for p in &points {
+                        let a = *p + Vec3::new(0., 1., 0.);
+                        let top = *p + Vec3::new(0., 2., 0.);
+                        let dir = Vec3::new(1., 0., 0.5);
+                        hits += query.capsule_cast(a, top, 1., dir, 2.).is_some() as usize;
+                        hits += query.raycast(top, Vec3::NEG_Y, 10.).is_some() as usize;
+                    }
+                    hits
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, build, surfaces_near, casts);
+criterion_main!(benches);
@@ -0,0 +1,21 @@
+use mcap::Vec3;
+use modelz::{Indices, Model3D};
+
+// every mesh's triangles, no raylib so the benches can share it
+pub fn triangles(scene: Model3D) -> Vec<[Vec3; 3]> {
+    let mut triangles = vec![];
+
+    for mesh in scene.meshes {
+        let ind: Vec<usize> = match mesh.indices.unwrap() {
+            Indices::U8(s) => s.iter().map(|i| *i as usize).collect(),
+            Indices::U16(s) => s.iter().map(|i| *i as usize).collect(),
+            Indices::U32(s) => s.iter().map(|i| *i as usize).collect(),
+        };
+
+        for tri in ind.chunks(3) {
+            triangles.push([0, 1, 2].map(|i| Vec3::from(mesh.vertices[tri[i]].position)));
+        }
+    }
+
+    triangles
+}
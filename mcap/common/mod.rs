@@ -1,36 +1,21 @@
-use modelz::{Indices, Model3D};
+use mcap::{
+    SKIN_FACTOR, Surface, Vec2, Vec3, check_circle_tri_collision, closest_point_on_segment_v2,
+    find_floor_height_hotdog_v4, ray_capsule_2d, triangle_slice_at_y,
+};
+use modelz::Model3D;
 use raylib::prelude::*;
-use glam::Vec3;
+
+mod gltf;
 
 #[allow(dead_code)]
 pub fn get_triangles(scene: Model3D) -> Vec<[Vector3; 3]> {
-    let mut surfaces = vec![];
-
-    for mesh in scene.meshes {
-        let ind: Vec<usize> = match mesh.indices.unwrap() {
-            Indices::U8(s) => s.iter().map(|i| *i as usize).collect(),
-            Indices::U16(s) => s.iter().map(|i| *i as usize).collect(),
-            Indices::U32(s) => s.iter().map(|i| *i as usize).collect(),
-        };
-
-        for tri in ind.chunks(3) {
-            let v1 = match mesh.vertices[tri[0]].position {
-                [x, y, z] => Vector3::from((x, y, z)),
-            };
-            let v2 = match mesh.vertices[tri[1]].position {
-                [x, y, z] => Vector3::from((x, y, z)),
-            };
-            let v3 = match mesh.vertices[tri[2]].position {
-                [x, y, z] => Vector3::from((x, y, z)),
-            };
-
-            surfaces.push([v1, v2, v3]);
-        }
-    }
-
-    surfaces
+    gltf::triangles(scene)
+        .iter()
+        .map(|t| t.map(|v| v.to_rayv3()))
+        .collect()
 }
 
+#[allow(dead_code)]
 pub trait ToVec3 {
     fn to_mcapv3(&self) -> Vec3;
 }
@@ -50,4 +35,140 @@ impl ToVector3 for Vec3 {
     fn to_rayv3(&self) -> Vector3 {
         Vector3::new(self.x, self.y, self.z)
     }
-}
\ No newline at end of file
+}
+// where a circle moving on xz stopped against the walls, and what's left
+// of the move slid along the one it hit
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct WallSlide {
+    pub dest_xz: Vec2,
+    pub next_move: Vec2,
+}
+
+// the walls crossing y, flattened to segments on xz
+#[allow(dead_code)]
+pub fn wall_slices(surfaces: &[&Surface], y: f32) -> Vec<(Vec2, Vec2, Vec2)> {
+    surfaces
+        .iter()
+        .filter_map(|s| match s {
+            Surface::Wall(w) => Some(w),
+            _ => None,
+        })
+        .filter_map(|w| {
+            let (a, b) = triangle_slice_at_y(&w.verts(), y)?;
+            Some((a, b, Vec2::new(w.normal().x, w.normal().z).normalize()))
+        })
+        .collect()
+}
+
+#[allow(dead_code)]
+pub fn inside_walls(p: Vec2, slices: &[(Vec2, Vec2, Vec2)], radius: f32) -> bool {
+    slices
+        .iter()
+        .any(|(a, b, _)| closest_point_on_segment_v2(p, *a, *b).distance(p) < radius)
+}
+
+// one collide-and-slide step of a circle from src to dst at src.y,
+// None if nothing's in the way
+#[allow(dead_code)]
+pub fn slide_walls(
+    src: Vec3,
+    dst: Vec3,
+    radius: f32,
+    original_dir: Vec3,
+    surfaces: &[&Surface],
+) -> Option<WallSlide> {
+    let slices = wall_slices(surfaces, src.y);
+    let r = radius * (1. + SKIN_FACTOR);
+    let src_xz = Vec2::new(src.x, src.z);
+    let dst_xz = Vec2::new(dst.x, dst.z);
+    let diff = dst_xz - src_xz;
+    let dir = diff.normalize_or_zero();
+
+    let (t, n) = slices
+        .iter()
+        .filter_map(|(a, b, _)| ray_capsule_2d(src_xz, dir, diff.length(), *a, *b, r))
+        .min_by(|x, y| x.0.total_cmp(&y.0))
+        // resting inside a wall at dst reads as a hit there
+        .or_else(|| {
+            slices
+                .iter()
+                .find(|(a, b, _)| closest_point_on_segment_v2(dst_xz, *a, *b).distance(dst_xz) < r)
+                .map(|(_, _, n)| (diff.length(), *n))
+        })?;
+
+    let dest_xz = step_back(src_xz, dst_xz, t, n, radius, &slices);
+
+    // slide along the wall, unless that turns us around
+    let step = dst_xz - dest_xz;
+    let mut next_move = step - step.project_onto(n);
+    let original_xz = Vec2::new(original_dir.x, original_dir.z);
+    if next_move.normalize_or_zero().dot(original_xz) < 0. {
+        next_move = Vec2::ZERO;
+    }
+
+    Some(WallSlide { dest_xz, next_move })
+}
+
+// the first of a few points from the hit at t back to src that's clear of the walls
+#[allow(dead_code)]
+pub fn step_back(
+    src_xz: Vec2,
+    dst_xz: Vec2,
+    t: f32,
+    n: Vec2,
+    radius: f32,
+    slices: &[(Vec2, Vec2, Vec2)],
+) -> Vec2 {
+    let skin = radius * SKIN_FACTOR;
+    if src_xz.distance(dst_xz) <= skin {
+        return src_xz;
+    }
+
+    let end = src_xz + (dst_xz - src_xz).normalize() * t - n * skin;
+    (0..4)
+        .map(|i| end.lerp(src_xz, i as f32 / 4.))
+        .find(|p| !inside_walls(*p, slices, radius + skin))
+        .unwrap_or(src_xz)
+}
+
+// step the feet, pushing out of walls at chest height and snapping to the floor
+#[allow(dead_code)]
+pub fn step_push(
+    pos: Vec3,
+    step: Vec3,
+    radius: f32,
+    chest_height: f32,
+    floor_snap_dist: f32,
+    surfaces: &[&Surface],
+) -> Vec3 {
+    let mut target = pos + step;
+
+    // a few rounds for corners, stop once nothing pushes
+    for _ in 0..4 {
+        let mut collided = false;
+        for s in surfaces {
+            let Surface::Wall(wall) = s else {
+                continue;
+            };
+            let chest = target.with_y(target.y + chest_height);
+            if let Some((push, _)) = check_circle_tri_collision(chest, radius, wall) {
+                target += push;
+                collided = true;
+            }
+        }
+
+        let snap = floor_snap_dist;
+        let Some((_, y)) = find_floor_height_hotdog_v4(target, snap, snap, surfaces, 0.) else {
+            // walked off a ledge
+            break;
+        };
+        target.y = y;
+
+        if !collided {
+            break;
+        }
+    }
+
+    target
+}
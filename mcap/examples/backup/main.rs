@@ -1,13 +1,12 @@
 use ::core::f32;
 
-use mcap::scrap as mcap;
-
-use glam::Vec2;
-use mcap::{
-    C2Ray, C2Raycast, HotDog, Surface, Vec3, get_face_normal, 
-};
+use mcap::{SKIN_FACTOR, Surface, Vec2, Vec3, get_face_normal};
 use raylib::prelude::*;
 
+#[path = "../../common/mod.rs"]
+mod common;
+use common::{step_back, wall_slices};
+
 fn main() {
     const SCREEN_W: i32 = 640;
     const SCREEN_H: i32 = 480;
@@ -63,13 +62,8 @@ fn main() {
         .map(|t| Surface::new([t[0], t[1], t[2]], get_face_normal(t[0], t[1], t[2])))
         .collect();
 
-    let wsurfs: Vec<_> = surfaces
-        .iter()
-        .filter_map(|s| match s {
-            Surface::Wall(w) => Some(w),
-            _ => None,
-        })
-        .collect();
+    let wsurfs: Vec<_> = surfaces.iter().collect();
+    let slices = wall_slices(&wsurfs, 0.);
 
     while !rl.window_should_close() {
         let fd = rl.get_frame_time();
@@ -127,25 +121,19 @@ fn main() {
                 d.draw_line_ex(s, e, 5., Color::BLACK);
             }
 
-            let lpos = Vec3::new(p_src.x, 0., p_src.y);
-            let ldst = Vec3::new(p_dst.x, 0., p_dst.y);
-            let step = ldst - lpos;
-            let move_len = step.length();
-            let move_dir = step.normalize();
-            let hotdog = HotDog::new(lpos, ldst, radius, move_dir);
-
-            let ray = C2Ray {
-                p: Vec2::new(p_src.x, p_src.y),
-                d: Vec2::new(move_dir.x, move_dir.z),
-                t: move_len,
-            };
-
-            let collision = C2Raycast {
-                n: Vec2::ONE.normalize(),
-                t: move_len,
-            };
-
-            let dest_xz = hotdog.step_back(ray, &collision, &wsurfs, Some(&mut d));
+            let src_xz = Vec2::new(p_src.x, p_src.y);
+            let dst_xz = Vec2::new(p_dst.x, p_dst.y);
+            let move_len = src_xz.distance(dst_xz);
+            let n = Vec2::ONE.normalize();
+
+            // where step_back tries, from the end back toward src
+            let end = dst_xz - n * radius * SKIN_FACTOR;
+            for i in 0..4 {
+                let p = end.lerp(src_xz, i as f32 / 4.);
+                d.draw_circle(p.x as i32, p.y as i32, radius, Color::ORANGE.alpha(0.5));
+            }
+
+            let dest_xz = step_back(src_xz, dst_xz, move_len, n, radius, &slices);
             d.draw_circle(
                 dest_xz.x as i32,
                 dest_xz.y as i32,
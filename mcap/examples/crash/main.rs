@@ -2,21 +2,13 @@
 // HotDog { src: Vec2(89.92252, 110.934685), srcv3: Vec3(89.92252, 96.15472, 110.934685), dst: Vec2(89.92252, 110.97825), skin: 0.001, radius: 1.0, y_dir: Vec2(0.0, 1.0), x_dir: Vec2(-1.0, 0.0), window: Window { x_min: -1.0, x_max: 1.0, y_min: 0.0, y_max: 0.0435638427734375 }, original_dir: Vec2(-0.0017964393, 0.99999845) }
 // thread 'main' panicked at src/li
 
-use mcap::scrap as mcap;
-
-use mcap::{HotDog, Surface, Vec3, get_face_normal};
+use mcap::{Surface, Vec3, get_face_normal};
 use raylib::prelude::*;
 mod triangles;
 
-trait ToVec3 {
-    fn to_mcapv3(&self) -> Vec3;
-}
-
-impl ToVec3 for Vector3 {
-    fn to_mcapv3(&self) -> Vec3 {
-        Vec3::new(self.x, self.y, self.z)
-    }
-}
+#[path = "../../common/mod.rs"]
+mod common;
+use common::{ToVec3, slide_walls};
 
 pub fn at_origin(v: Vector3) -> Vector3 {
     v + Vector3::one() * 100.
@@ -48,8 +40,7 @@ fn main() {
     let dst = Vec3::new(89.92245, src.y, 110.97825);
     let org = Vec3::new(-0.0017964393, 0., 0.99999845);
 
-    let hotdog = HotDog::new(src, dst, 1.0, org);
-    let s = hotdog.check_walls_c2(&surfaces);
+    let s = slide_walls(src, dst, 1.0, org, &surfaces);
 
     let hdc = s.unwrap();
     let n_src = Vec3::new(hdc.dest_xz.x, src.y, hdc.dest_xz.y);
@@ -61,8 +52,7 @@ fn main() {
     eprintln!("n_d: {:?}", n_dst);
     eprintln!("hdcnm: {:?}", hdc.next_move);
 
-    let hotdog = HotDog::new(n_src, n_dst, 1.0, org);
-    let s = hotdog.check_walls_c2(&surfaces);
+    let s = slide_walls(n_src, n_dst, 1.0, org, &surfaces);
 
     eprintln!("s: {:?}", s);
 }
@@ -1,8 +1,6 @@
-use mcap::{HotDog, Surface, Triangle, Vec3};
+use mcap::{Surface, Triangle, Vec3, push_out_walls_2};
 use raylib::prelude::*;
 
-use mcap::scrap as mcap;
-
 #[path = "../../common/mod.rs"]
 mod common;
 use common::{ToVec3, ToVector3};
@@ -27,16 +25,13 @@ fn main() {
 
     let origin = at_origin(Vector3::zero());
 
-    let tri = Triangle {
-        verts: [
-            Vec3::new(80.0, 95.0, 120.0),
-            Vec3::new(80.0, 95.0, 80.0),
-            Vec3::new(80.0, 105.0, 80.0),
-        ],
-        normal: Vec3::new(1.0, 0.0, 0.0),
-        origin_offset: -80.0,
-    };
-    let surfaces = vec![Surface::new(tri.verts(), tri.normal)];
+    let verts = [
+        Vec3::new(80.0, 95.0, 120.0),
+        Vec3::new(80.0, 95.0, 80.0),
+        Vec3::new(80.0, 105.0, 80.0),
+    ];
+    let surfaces = vec![Surface::new(verts, Vec3::new(1.0, 0.0, 0.0))];
+    let tri = *surfaces[0].triangle();
 
     let surfaces: Vec<_> = surfaces.iter().map(|s| s).collect();
 
@@ -55,9 +50,6 @@ fn main() {
     let mut total = 0.;
     let mut fc: f32 = 0.;
 
-    let mut last_hotdog = None;
-    let mut fail_open = false;
-
     while !rl.window_should_close() {
         let fd = rl.get_frame_time();
         let fps = rl.get_fps();
@@ -96,69 +88,19 @@ fn main() {
         let move_speed = 10.0;
         let move_dir = (forward_dir * ws + right_dir * ad).normalized();
 
-        let src = player.pos + Vector3::new(0., player.chest_height, 0.);
-        let dst = src + (move_dir * move_speed * fd);
-
-        let max_iter = 5;
-        // let src = Vector3::new(81.000046, 99.75, 88.801704);
-        // let dst = Vector3::new(80.99945, src.y, 89.13504);
-        // let move_dir = (dst - src).normalized();
-
-        let mut lpos = src.to_mcapv3();
-        let mut ldst = dst.to_mcapv3();
-        let mut lout = ldst.with_y(ldst.y - player.chest_height);
-
-        for i in 0..max_iter {
-            let hotdog = HotDog::new(lpos, ldst, player.radius, move_dir.to_mcapv3());
-
-            // on no-collision or no-move
-            let mut exit_early = true;
-
-            if let Some(hdc) = hotdog.check_walls_c2(&surfaces) {
-                exit_early = false;
-
-                eprintln!("hotdog: {:?} hdc: {:?}", hotdog, hdc);
-
-                // update current position
-                lpos = Vec3::new(hdc.dest_xz.x, lpos.y, hdc.dest_xz.y);
-
-                // set up next destination
-                ldst = lpos + Vec3::new(hdc.next_move.x, 0., hdc.next_move.y);
+        let dst = player.pos + (move_dir * move_speed * fd);
 
-                // bailing on no-move collision
-                if hdc.next_move_len == 0. {
-                    ldst = lpos;
-                    exit_early = true;
-                }
-
-                // if final collision, ditch remaining dst
-                if i == max_iter - 1 {
-                    fail_open = true;
-                    ldst = lpos;
-                }
-
-                // if next move is basically 0, exit after floor check
-                if hdc.next_move_len < f32::EPSILON {
-                    ldst = lpos;
-                    exit_early = true;
-                }
-
-                if fail_open {
-                    eprintln!("hotdog: {:?}", hotdog);
-                    eprintln!("last_hotdog: {:?}", last_hotdog);
-                }
-
-                last_hotdog = Some(hotdog);
-            }
-
-            lout = ldst.with_y(ldst.y - player.chest_height);
-
-            if exit_early {
-                break;
-            }
+        let (out, hit, _) = push_out_walls_2(
+            dst.to_mcapv3(),
+            player.chest_height,
+            player.radius,
+            &surfaces,
+        );
+        if hit {
+            eprintln!("pushed out of wall at {:?}", out);
         }
 
-        player.pos = lout.to_rayv3();
+        player.pos = out.to_rayv3();
 
         // calculate cam pos
         let player_top = player.pos + Vector3::new(0., player.height, 0.);
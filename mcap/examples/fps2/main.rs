@@ -1,14 +1,10 @@
-use mcap::{
-    Surface, Triangle, get_face_normal, get_step_push, get_step_push_m64,
-    get_step_push_most_opposing, get_step_push_oot,
-};
+use mcap::{Surface, Triangle, Vec3, get_face_normal, push_out_walls_2};
 use modelz;
 use raylib::prelude::*;
-use mcap::scrap as mcap;
 
 #[path = "../../common/mod.rs"]
 mod common;
-use common::{ToVec3, ToVector3, get_triangles};
+use common::{ToVec3, ToVector3, get_triangles, step_push};
 
 pub fn at_origin(v: Vector3) -> Vector3 {
     v + Vector3::one() * 100.
@@ -102,31 +98,24 @@ fn main() {
 
         #[derive(Debug)]
         enum CollFunc {
-            GetStepPushM64,
-            GetStepPush,
-            GetStepPushMostOpposing,
-            GetStepPushOot,
+            StepPush,
+            PushOutWalls2,
             __END,
         }
         if rl.is_key_pressed(KeyboardKey::KEY_N) {
             findex = (findex + 1) % CollFunc::__END as usize;
         }
-        let coll_func = vec![
-            get_step_push_m64,
-            get_step_push,
-            get_step_push_most_opposing,
-            get_step_push_oot,
-        ][findex];
-        let coll_func_debug = &vec![
-            CollFunc::GetStepPushM64,
-            CollFunc::GetStepPush,
-            CollFunc::GetStepPushMostOpposing,
-            CollFunc::GetStepPushOot,
-        ][findex];
+        // push_out_walls_2 doesn't snap to floors
+        let push_out: fn(Vec3, Vec3, f32, f32, f32, &[&Surface]) -> Vec3 =
+            |pos, step, radius, chest_height, _, surfaces| {
+                push_out_walls_2(pos + step, chest_height, radius, surfaces).0
+            };
+        let coll_func = vec![step_push, push_out][findex];
+        let coll_func_debug = &vec![CollFunc::StepPush, CollFunc::PushOutWalls2][findex];
 
         let tic = movement / iterations as f32;
         for _ in 0..iterations {
-            let res = coll_func(
+            let pos = coll_func(
                 player.pos.to_mcapv3(),
                 tic.to_mcapv3(),
                 player.radius,
@@ -135,7 +124,7 @@ fn main() {
                 &surfaces,
             );
 
-            player.pos = res.final_pos.to_rayv3();
+            player.pos = pos.to_rayv3();
         }
 
         // calculate cam pos
@@ -1,33 +1,12 @@
-use mcap::scrap as mcap;
-
-use mcap::{
-    HotDogv2, Surface, Triangle, Vec3,
-    find_floor_height_hotdog_v2, get_face_normal,
-};
+use mcap::{Surface, Triangle, Vec3, find_floor_height_hotdog_v4, get_face_normal};
 use modelz;
 use raylib::prelude::*;
 
 mod triangles;
 
-trait ToVec3 {
-    fn to_mcapv3(&self) -> Vec3;
-}
-
-trait ToVector3 {
-    fn to_rayv3(&self) -> Vector3;
-}
-
-impl ToVec3 for Vector3 {
-    fn to_mcapv3(&self) -> Vec3 {
-        Vec3::new(self.x, self.y, self.z)
-    }
-}
-
-impl ToVector3 for Vec3 {
-    fn to_rayv3(&self) -> Vector3 {
-        Vector3::new(self.x, self.y, self.z)
-    }
-}
+#[path = "../../common/mod.rs"]
+mod common;
+use common::{ToVec3, ToVector3, slide_walls};
 
 pub fn at_origin(v: Vector3) -> Vector3 {
     v + Vector3::one() * 100.
@@ -159,45 +138,18 @@ fn main() {
         let mut ldst = dst.to_mcapv3();
         let mut lout = ldst;
         for i in 0..max_iter {
-            // the body of this should be a function to invoke
-            let hotdog_top = HotDogv2::new(
-                lpos,
+            // checked just under the top of the player
+            let first_hdc = slide_walls(
+                lpos.with_y(lpos.y + player.height - 0.002),
                 ldst,
                 player.radius,
-                player.height - 0.002,
-                0.002,
-                move_dir.to_mcapv3(),
-            );
-            let _hotdog_bot = HotDogv2::new(
-                lpos,
-                ldst,
-                player.radius,
-                player.snap + 0.002,
-                0.002,
                 move_dir.to_mcapv3(),
+                &walls,
             );
 
             // on no-collision or no-move
             let mut exit_early = true;
 
-            // I don't think this is gonna work out
-            let first_hdc = match (
-                hotdog_top.check_walls_c2(&walls),
-                None,
-                // hotdog_bot.check_walls_c2(&walls)
-            ) {
-                (None, None) => None,
-                (Some(hdc), None) => Some(hdc),
-                (None, Some(hdc)) => Some(hdc),
-                (Some(hdc1), Some(hdc2)) => {
-                    if hdc1.next_move_len < hdc2.next_move_len {
-                        Some(hdc1)
-                    } else {
-                        Some(hdc2)
-                    }
-                }
-            };
-
             if let Some(hdc) = first_hdc {
                 exit_early = false;
 
@@ -213,7 +165,7 @@ fn main() {
                 }
 
                 // if next move is basically 0, exit after floor check
-                if hdc.next_move_len < f32::EPSILON {
+                if hdc.next_move.length() < f32::EPSILON {
                     ldst = lpos;
                     exit_early = true;
                 }
@@ -224,7 +176,7 @@ fn main() {
             // if let Some((floor, y)) = find_floor_height_closest_v2(lout, snap, &floors, player.radius) {
             // if let Some((floor, y)) = find_floor_height_hotdog(lout, snap, &floors, player.radius) {
             if let Some((floor, y)) =
-                find_floor_height_hotdog_v2(lout, player.snap, &floors, player.radius)
+                find_floor_height_hotdog_v4(lout, player.snap, player.snap, &floors, player.radius)
             {
                 // if let Some((floor, y)) = find_floor_height_m64(lout, snap, &floors) {
                 lout.y = y - player.radius * 0.001;
@@ -233,7 +185,7 @@ fn main() {
                 // step.y = 0.;
                 // step -= floor.normal * step.dot(floor.normal);
                 player.airborne = false;
-                floor_draw = Some(floor);
+                floor_draw = Some(*floor.triangle());
             } else {
                 // walked off ledge, become airborne
                 player.airborne = true;
@@ -255,16 +207,13 @@ fn main() {
         let mut lout = lout;
 
         if player.airborne {
-            let hotdog = HotDogv2::new(
-                lpos,
+            if let Some(hdc) = slide_walls(
+                lpos.with_y(lpos.y + player.height - 0.002),
                 ldst,
                 player.radius,
-                player.height - 0.002,
-                0.002,
                 move_dir.to_mcapv3(),
-            );
-
-            if let Some(hdc) = hotdog.check_walls_c2(&walls) {
+                &walls,
+            ) {
                 // update current position
                 lpos = Vec3::new(hdc.dest_xz.x, lpos.y, hdc.dest_xz.y);
 
@@ -272,7 +221,7 @@ fn main() {
                 ldst = lpos + Vec3::new(hdc.next_move.x, 0., hdc.next_move.y);
 
                 // if next move is basically 0, exit after floor check
-                if hdc.next_move_len < f32::EPSILON {
+                if hdc.next_move.length() < f32::EPSILON {
                     ldst = lpos;
                 }
             }
@@ -282,7 +231,7 @@ fn main() {
             // if let Some((floor, y)) = find_floor_height_closest_v2(lout, snap, &floors, player.radius) {
             // if let Some((floor, y)) = find_floor_height_hotdog(lout, snap, &floors, player.radius) {
             if let Some((floor, y)) =
-                find_floor_height_hotdog_v2(lout, player.snap, &floors, player.radius)
+                find_floor_height_hotdog_v4(lout, player.snap, player.snap, &floors, player.radius)
             {
                 // if let Some((floor, y)) = find_floor_height_m64(lout, snap, &floors) {
                 lout.y = y - player.radius * 0.001;
@@ -291,7 +240,7 @@ fn main() {
                 // step.y = 0.;
                 // step -= floor.normal * step.dot(floor.normal);
                 player.airborne = false;
-                floor_draw = Some(floor);
+                floor_draw = Some(*floor.triangle());
             } else {
                 // walked off ledge, become airborne
                 player.airborne = true;
@@ -408,7 +357,7 @@ fn main() {
             );
             d.draw_text(&format!("fps: {}", fps), 20, 60, 20, Color::WHITE);
             d.draw_text(&format!("avg: {:.0}", total / fc), 20, 80, 20, Color::WHITE);
-            d.draw_text(&format!("func: HotDogWalls"), 20, 100, 20, Color::WHITE);
+            d.draw_text(&format!("func: slide_walls"), 20, 100, 20, Color::WHITE);
             d.draw_text(
                 &format!("airborn: {:?}", player.airborne),
                 20,
@@ -1,33 +1,13 @@
-use mcap::scrap as mcap;
-
-use mcap::{
-    HotDog, Surface, Triangle, Vec3, find_floor_height_m64, get_face_normal, print_fi,
-};
+use mcap::{Surface, Triangle, Vec3, find_floor_height_hotdog_v4, get_face_normal};
 use modelz;
 use rand::Rng;
 use raylib::prelude::*;
 
 mod triangles;
 
-trait ToVec3 {
-    fn to_mcapv3(&self) -> Vec3;
-}
-
-trait ToVector3 {
-    fn to_rayv3(&self) -> Vector3;
-}
-
-impl ToVec3 for Vector3 {
-    fn to_mcapv3(&self) -> Vec3 {
-        Vec3::new(self.x, self.y, self.z)
-    }
-}
-
-impl ToVector3 for Vec3 {
-    fn to_rayv3(&self) -> Vector3 {
-        Vector3::new(self.x, self.y, self.z)
-    }
-}
+#[path = "../../common/mod.rs"]
+mod common;
+use common::{ToVec3, ToVector3, slide_walls};
 
 pub fn at_origin(v: Vector3) -> Vector3 {
     v + Vector3::one() * 100.
@@ -168,12 +148,12 @@ fn main() {
         let mut ldst = dst.to_mcapv3();
         let mut lout = ldst.with_y(ldst.y - player.chest_height);
         for i in 0..max_iter {
-            let hotdog = HotDog::new(lpos, ldst, player.radius, move_dir.to_mcapv3());
+            let hdc = slide_walls(lpos, ldst, player.radius, move_dir.to_mcapv3(), &walls);
 
             // on no-collision or no-move
             let mut exit_early = true;
 
-            if let Some(hdc) = hotdog.check_walls_c2(&walls) {
+            if let Some(hdc) = hdc {
                 exit_early = false;
 
                 // update current position
@@ -189,7 +169,7 @@ fn main() {
                 }
 
                 // if next move is basically 0, exit after floor check
-                if hdc.next_move_len < f32::EPSILON {
+                if hdc.next_move.length() < f32::EPSILON {
                     ldst = lpos;
                     exit_early = true;
                 }
@@ -198,7 +178,7 @@ fn main() {
             lout = ldst.with_y(ldst.y - player.chest_height);
 
             let snap = player.height - player.chest_height;
-            if let Some((_floor, y)) = find_floor_height_m64(lout, snap, &floors) {
+            if let Some((_floor, y)) = find_floor_height_hotdog_v4(lout, snap, snap, &floors, 0.) {
                 lout.y = y - player.radius * 0.001;
                 // // todo, apply this to inter-frame velocity
                 // // zero out y, project step onto floor normal
@@ -314,9 +294,7 @@ fn main() {
             );
             d.draw_text(&format!("fps: {}", fps), 20, 60, 20, Color::WHITE);
             d.draw_text(&format!("avg: {:.0}", total / fc), 20, 80, 20, Color::WHITE);
-            d.draw_text(&format!("func: HotDogWalls"), 20, 100, 20, Color::WHITE);
+            d.draw_text(&format!("func: slide_walls"), 20, 100, 20, Color::WHITE);
         }
     }
-
-    print_fi();
 }
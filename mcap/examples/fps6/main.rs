@@ -2,7 +2,7 @@ use std::collections::VecDeque;
 use rand::Rng;
 
 use mcap::{
-    GRID_SIZE, Surface, SurfaceGrid, Triangle, Vec3, find_ciel_height_hotdog_v3, find_floor_height_hotdog_v4, push_out_walls_2 
+    GRID_SIZE, cell_of, Surface, SurfaceGrid, Triangle, Vec3, find_ciel_height_hotdog_v3, find_floor_height_hotdog_v4, push_out_walls_2 
 };
 use modelz;
 use raylib::prelude::*;
@@ -348,10 +348,7 @@ fn main() {
 
             let mut pos = (player.pos + move_dist).to_mcapv3();
 
-            grid_pos = {
-                let fgpos = pos / GRID_SIZE;
-                (fgpos.x.floor() as u32, fgpos.y.floor() as u32, fgpos.z.floor() as u32 )
-            };
+            grid_pos = cell_of(pos);
 
             collision_surfaces = match collision_mode {
                 CollisionMode::All => levels.grid().all_surfaces().unwrap_or_default(),
@@ -459,7 +456,7 @@ fn main() {
                     if draw_surfs { draw_surf(&mut d3d, &ft, Color::ORANGE.alpha(0.5)) };
                 }
                 if show_grid {
-                    let igrid = (grid_pos.0 as i32, grid_pos.1 as i32, grid_pos.2 as i32);
+                    let igrid = grid_pos;
 
                     for dx in -2i32..=2 {
                         let x = (igrid.0 + dx) as f32;
@@ -2,13 +2,12 @@
 // HotDog { src: Vec2(89.92252, 110.934685), srcv3: Vec3(89.92252, 96.15472, 110.934685), dst: Vec2(89.92252, 110.97825), skin: 0.001, radius: 1.0, y_dir: Vec2(0.0, 1.0), x_dir: Vec2(-1.0, 0.0), window: Window { x_min: -1.0, x_max: 1.0, y_min: 0.0, y_max: 0.0435638427734375 }, original_dir: Vec2(-0.0017964393, 0.99999845) }
 // thread 'main' panicked at src/li
 
-use mcap::scrap as mcap;
-use mcap::{HotDog, Surface, Vec3, get_face_normal};
+use mcap::{Surface, Vec3, get_face_normal};
 use raylib::prelude::*;
 
 #[path = "../../common/mod.rs"]
 mod common;
-use common::{ToVec3, get_triangles};
+use common::{ToVec3, get_triangles, slide_walls};
 
 pub fn at_origin(v: Vector3) -> Vector3 {
     v + Vector3::one() * 100.
@@ -48,8 +47,7 @@ fn main() {
     let dst = Vec3::new(89.92245, src.y, 110.97825);
     let org = Vec3::new(-0.0017964393, 0., 0.99999845);
 
-    let hd1 = HotDog::new(src, dst, 1.0, org);
-    let hdc1 = hd1.check_walls_c2(&surfaces).unwrap();
+    let hdc1 = slide_walls(src, dst, 1.0, org, &surfaces).unwrap();
     let src1 = Vec3::new(hdc1.dest_xz.x, src.y, hdc1.dest_xz.y);
     let dst1 = src1 + Vec3::new(hdc1.next_move.x, 0., hdc1.next_move.y);
 
@@ -59,8 +57,7 @@ fn main() {
     eprintln!("n_d: {:?}", dst1);
     eprintln!("hdcnm: {:?}", hdc1.next_move);
 
-    let hd2 = HotDog::new(src1, dst1, 1.0, org);
-    let hdc2 = hd2.check_walls_c2(&surfaces).unwrap();
+    let hdc2 = slide_walls(src1, dst1, 1.0, org, &surfaces).unwrap();
     let src2 = Vec3::new(hdc2.dest_xz.x, src.y, hdc2.dest_xz.y);
     let dst2 = src2 + Vec3::new(hdc2.next_move.x, 0., hdc2.next_move.y);
 
@@ -75,7 +72,7 @@ fn main() {
         _ => unreachable!(),
     };
 
-    let verts = wall.verts;
+    let verts = wall.verts();
     let p0 = Vector2::new(verts[0].x, verts[0].z);
     let p1 = Vector2::new(verts[1].x, verts[1].z);
     let p2 = Vector2::new(verts[2].x, verts[2].z);
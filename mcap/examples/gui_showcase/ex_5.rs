@@ -1,7 +1,7 @@
+use crate::common::step_push;
 use crate::{Args, Example, Shape, ToVec3, ToVector3, at_origin};
-use mcap::{Surface, get_face_normal, get_step_push};
+use mcap::{Surface, get_face_normal};
 use raylib::prelude::*;
-use mcap::scrap as mcap;
 
 pub struct State {
     cam_start_pos: Vector3,
@@ -107,15 +107,15 @@ impl Example for State {
         let mut new_pos = self.start_pos;
 
         for i in 0..iterations {
-            let res = get_step_push(
+            new_pos = step_push(
                 new_pos.to_mcapv3(),
                 v_chunk.to_mcapv3(),
                 1.,
                 3.,
                 0.4,
                 &surfs,
-            );
-            new_pos = res.final_pos.to_rayv3();
+            )
+            .to_rayv3();
 
             // push wires at updated position
             out.push((
@@ -1,7 +1,6 @@
 use crate::{Args, Example, Shape, ToVec3, ToVector3, at_origin};
 use mcap::{Surface, get_face_normal};
 use raylib::prelude::*;
-use mcap::scrap as mcap;
 
 pub struct State {
     cam_start_pos: Vector3,
@@ -1,10 +1,6 @@
 use crate::{Args, Example, Shape, ToVec3, ToVector3, at_origin};
-use glam::Vec3;
-use mcap::{
-    Surface, find_floor_height_m64, get_face_normal,
-};
+use mcap::{Surface, Vec3, find_floor_height_hotdog_v4, get_face_normal};
 use raylib::prelude::*;
-use mcap::scrap as mcap;
 
 pub struct State {
     cam_start_pos: Vector3,
@@ -113,8 +109,8 @@ impl Example for State {
             Color::YELLOW,
         ));
 
-        if let Some((a, b)) = find_floor_height_m64(base, snap, &surfs) {
-            let v = a.verts();
+        if let Some((a, b)) = find_floor_height_hotdog_v4(base, snap, snap, &surfs, 0.) {
+            let v = a.triangle().verts();
             out.push((
                 Shape::Triangle([v[0].to_rayv3(), v[1].to_rayv3(), v[2].to_rayv3()]),
                 Color::RED,
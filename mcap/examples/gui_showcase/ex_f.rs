@@ -1,8 +1,7 @@
+use crate::common::slide_walls;
 use crate::{Args, Example, Shape, ToVec3, at_origin};
-use glam::Vec3;
-use mcap::{HotDog, Surface, get_face_normal};
+use mcap::{Surface, Vec3, get_face_normal};
 use raylib::prelude::*;
-use mcap::scrap as mcap;
 
 pub struct State {
     cam_start_pos: Vector3,
@@ -131,14 +130,14 @@ impl Example for State {
 
         let src = self.start_pos;
         let dst = self.start_pos + self.velocity;
-        let hotdog = HotDog::new(
+        let hdc1 = slide_walls(
             src.to_mcapv3(),
             dst.to_mcapv3(),
             1.0,
             self.velocity.normalized().to_mcapv3(),
-        );
-
-        let hdc1 = hotdog.check_walls_c2(&surfs).unwrap();
+            &surfs,
+        )
+        .unwrap();
 
         // stopped pos
         out.push((
@@ -170,9 +169,9 @@ impl Example for State {
             hdc1.dest_xz.y + hdc1.next_move.y,
         );
         let hd2_dst = hd2_src + Vec3::new(hdc1.next_move.x, 0., hdc1.next_move.y);
-        let hotdog2 = HotDog::new(hd2_src, hd2_dst, 1., self.velocity.normalized().to_mcapv3());
+        let dir = self.velocity.normalized().to_mcapv3();
 
-        let hdc2 = hotdog2.check_walls_c2(&surfs);
+        let hdc2 = slide_walls(hd2_src, hd2_dst, 1., dir, &surfs);
         if let Some(hdc2) = hdc2 {
             // stopped pos
             out.push((
@@ -190,8 +189,7 @@ impl Example for State {
                 hdc2.dest_xz.y + hdc2.next_move.y,
             );
             let hd3_dst = hd3_src + Vec3::new(hdc2.next_move.x, 0., hdc2.next_move.y);
-            let hotdog3 = HotDog::new(hd3_src, hd3_dst, 1., self.velocity.normalized().to_mcapv3());
-            assert!(hotdog3.check_walls_c2(&surfs).is_none());
+            assert!(slide_walls(hd3_src, hd3_dst, 1., dir, &surfs).is_none());
         }
 
         // push wires at original intended position
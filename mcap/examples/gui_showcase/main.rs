@@ -61,6 +61,24 @@ struct Args {
     time: f64,
 }
 
+const EXAMPLES: &[fn() -> Box<dyn Example>] = &[
+    || Box::new(ex_1::State::new()),
+    || Box::new(ex_2::State::new()),
+    || Box::new(ex_3::State::new()),
+    || Box::new(ex_4::State::new()),
+    || Box::new(ex_5::State::new()),
+    || Box::new(ex_6::State::new()),
+    || Box::new(ex_7::State::new()),
+    || Box::new(ex_8::State::new()),
+    || Box::new(ex_9::State::new()),
+    || Box::new(ex_a::State::new()),
+    || Box::new(ex_b::State::new()),
+    || Box::new(ex_c::State::new()),
+    || Box::new(ex_d::State::new()),
+    || Box::new(ex_e::State::new()),
+    || Box::new(ex_f::State::new()),
+];
+
 trait Example {
     fn camera_start_pos(&mut self) -> Vector3;
     fn camera_start_tgt(&mut self) -> Vector3;
@@ -79,7 +97,7 @@ fn main() {
     );
 
     let mut example_id: usize = 0;
-    let mut example: Box<dyn Example> = Box::new(ex_1::State::new());
+    let mut example: Box<dyn Example> = EXAMPLES[0]();
 
     let mut cam_angle = Vector2::new(0., 0.);
 
@@ -117,31 +135,13 @@ fn main() {
 
         let mut d = rl.begin_drawing(&thread);
 
-        const NUM_EXAMPLES: usize = 0xF;
         if change != 0 {
             if prev {
-                example_id = example_id.checked_sub(1).unwrap_or(NUM_EXAMPLES - 1);
+                example_id = example_id.checked_sub(1).unwrap_or(EXAMPLES.len() - 1);
             } else if next {
-                example_id = (example_id + 1) % NUM_EXAMPLES;
+                example_id = (example_id + 1) % EXAMPLES.len();
             }
-            example = match example_id {
-                0x0 => Box::new(ex_1::State::new()),
-                0x1 => Box::new(ex_2::State::new()),
-                0x2 => Box::new(ex_3::State::new()),
-                0x3 => Box::new(ex_4::State::new()),
-                0x4 => Box::new(ex_5::State::new()),
-                0x5 => Box::new(ex_6::State::new()),
-                0x6 => Box::new(ex_7::State::new()),
-                0x7 => Box::new(ex_8::State::new()),
-                0x8 => Box::new(ex_9::State::new()),
-                0x9 => Box::new(ex_a::State::new()),
-                0xA => Box::new(ex_b::State::new()),
-                0xB => Box::new(ex_c::State::new()),
-                0xC => Box::new(ex_d::State::new()),
-                0xD => Box::new(ex_e::State::new()),
-                0xE => Box::new(ex_f::State::new()),
-                _ => panic!(),
-            };
+            example = EXAMPLES[example_id]();
 
             camera.position = example.camera_start_pos();
             camera.target = example.camera_start_tgt();
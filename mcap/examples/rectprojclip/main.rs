@@ -1,13 +1,104 @@
 use ::core::f32;
-use mcap::scrap as mcap;
 
-use glam::Vec2;
-use line_clipping::{LineSegment, Point};
-use mcap::{
-    HotDog, Surface, Vec3, get_face_normal
-};
+use line_clipping::cohen_sutherland::clip_line;
+use line_clipping::{LineSegment, Point, Window};
+use mcap::{Surface, Vec2, Vec3, closest_point_on_segment_v2, get_face_normal};
 use raylib::prelude::*;
 
+#[path = "../../common/mod.rs"]
+mod common;
+use common::slide_walls;
+
+// the rectangle swept by a circle from src to dst, x across it and y along it
+struct SweptRect {
+    src: Vec2,
+    radius: f32,
+    y_dir: Vec2,
+    x_dir: Vec2,
+    window: Window,
+}
+
+impl SweptRect {
+    fn new(src: Vec3, dst: Vec3, radius: f32) -> Self {
+        let src = Vec2::new(src.x, src.z);
+        let diff = Vec2::new(dst.x, dst.z) - src;
+        let y_dir = diff.normalize();
+        Self {
+            src,
+            radius,
+            y_dir,
+            x_dir: Vec2::new(-y_dir.y, y_dir.x),
+            window: Window::new(-radius as f64, radius as f64, 0., diff.length() as f64),
+        }
+    }
+
+    fn clip_line_segment(&self, p1: Vec2, p2: Vec2) -> Option<(Vec2, Vec2)> {
+        let rp1 = self.origin_point_to_rect_space(p1);
+        let rp2 = self.origin_point_to_rect_space(p2);
+        let l = clip_line(
+            LineSegment {
+                p1: Point {
+                    x: rp1.x as f64,
+                    y: rp1.y as f64,
+                },
+                p2: Point {
+                    x: rp2.x as f64,
+                    y: rp2.y as f64,
+                },
+            },
+            self.window,
+        )?;
+        Some((
+            self.rect_point_to_origin_space(Vec2::new(l.p1.x as f32, l.p1.y as f32)),
+            self.rect_point_to_origin_space(Vec2::new(l.p2.x as f32, l.p2.y as f32)),
+        ))
+    }
+
+    fn closest_point_on_segment_rect(&self, a: Vec2, b: Vec2) -> Vec2 {
+        let a = self.origin_point_to_rect_space(a);
+        let b = self.origin_point_to_rect_space(b);
+
+        closest_point_on_segment_v2(Vec2::ZERO, a, b)
+    }
+
+    fn origin_point_to_rect_space(&self, p: Vec2) -> Vec2 {
+        let p_trans = p - self.src;
+        Vec2::new(p_trans.dot(self.x_dir), p_trans.dot(self.y_dir))
+    }
+
+    fn rect_point_to_origin_space(&self, p: Vec2) -> Vec2 {
+        self.src + self.x_dir * p.x + self.y_dir * p.y
+    }
+
+    // nearest point to a circle collision, the line already clipped to the rect,
+    // not exact but close enough to draw
+    fn closest_point_on_segment_rect_circ(&self, a: Vec2, b: Vec2) -> Vec2 {
+        let a = self.origin_point_to_rect_space(a);
+        let b = self.origin_point_to_rect_space(b);
+
+        let closest_to_src = closest_point_on_segment_v2(Vec2::ZERO, a, b);
+
+        let tri = [
+            Vec3::new(a.x, 0., a.y),
+            Vec3::new(b.x, 1., b.y),
+            Vec3::new(b.x, 0., b.y),
+        ];
+        let norm = get_face_normal(tri[0], tri[1], tri[2]);
+
+        // back up by the z of the norm
+        let mut out = Vec2::new(0., closest_to_src.y - self.radius * norm.z);
+
+        // still inside the radius of an endpoint, bump further back
+        let a_dist = out.distance(a);
+        let b_dist = out.distance(b);
+        let min_dist = a_dist.min(b_dist);
+        if min_dist < self.radius {
+            out.y -= self.radius - min_dist;
+        }
+        out
+    }
+}
+
 fn main() {
     const SCREEN_W: i32 = 1280;
     const SCREEN_H: i32 = 960;
@@ -136,9 +227,9 @@ fn main() {
             // hotdog
             let src = Vec3::new(p_src.x, 0., p_src.y);
             let dst = Vec3::new(p_dst.x, 0., p_dst.y);
-            let hotdog = HotDog::new(src, dst, radius, (dst - src).normalize());
+            let hotdog = SweptRect::new(src, dst, radius);
 
-            let window = hotdog.get_window();
+            let window = hotdog.window;
 
             // draw points in origin space
             d.draw_circle(p1.x as i32, p1.y as i32, 3., Color::ORANGE);
@@ -168,17 +259,20 @@ fn main() {
             }
 
             // closest point origin
-            let cp1 = hotdog.closest_point_on_segment_origin(
+            let cp1 = closest_point_on_segment_v2(
+                Vec2::ZERO,
                 Vec2::new(p1.x as f32, p1.y as f32),
                 Vec2::new(p2.x as f32, p2.y as f32),
             );
             d.draw_circle(cp1.x as i32, cp1.y as i32, 3., Color::GREEN);
-            let cp2 = hotdog.closest_point_on_segment_origin(
+            let cp2 = closest_point_on_segment_v2(
+                Vec2::ZERO,
                 Vec2::new(p2.x as f32, p2.y as f32),
                 Vec2::new(p3.x as f32, p3.y as f32),
             );
             d.draw_circle(cp2.x as i32, cp2.y as i32, 3., Color::GREEN);
-            let cp3 = hotdog.closest_point_on_segment_origin(
+            let cp3 = closest_point_on_segment_v2(
+                Vec2::ZERO,
                 Vec2::new(p3.x as f32, p3.y as f32),
                 Vec2::new(p1.x as f32, p1.y as f32),
             );
@@ -266,7 +360,7 @@ fn main() {
                 }
             }
 
-            let res = hotdog.check_walls_c2(&wsurfs);
+            let res = slide_walls(src, dst, radius, (dst - src).normalize(), &wsurfs);
             // assert_eq!(res.is_none(), nearest.is_none());
 
             // origin space normals
@@ -1,12 +1,12 @@
 use ::core::f32;
-use mcap::scrap as mcap;
-
 use line_clipping::{LineSegment, Point};
-use mcap::{
-    HotDog, Surface, Vec3, get_face_normal,
-};
+use mcap::{Surface, Vec3, get_face_normal};
 use raylib::prelude::*;
 
+#[path = "../../common/mod.rs"]
+mod common;
+use common::slide_walls;
+
 fn main() {
     const SCREEN_W: i32 = 640;
     const SCREEN_H: i32 = 480;
@@ -217,7 +217,7 @@ fn main() {
 
             let max_iter = 4;
             let cs = Color::YELLOW.lerp(Color::GREEN, 1.0 / (max_iter as f32 + 1.0));
-            let ce = Color::YELLOW.lerp(Color::GREEN, max_iter as f32 / max_iter as f32 + 1.0);
+            let ce = Color::YELLOW.lerp(Color::GREEN, max_iter as f32 / (max_iter as f32 + 1.0));
             let colors: Vec<Color> = (0..max_iter)
                 .map(|i| cs.lerp(ce, i as f32 / max_iter as f32))
                 .collect();
@@ -229,13 +229,13 @@ fn main() {
             let mut lsrc = src;
             let mut ldst = dst;
             for i in 0..max_iter {
-                let hotdog = HotDog::new(
+                if let Some(hdc) = slide_walls(
                     lsrc,
                     ldst,
                     radius,
                     Vec3::new(starting_dir.x, 0., starting_dir.y),
-                );
-                if let Some(hdc) = hotdog.check_walls_c2(&wsurfs) {
+                    &wsurfs,
+                ) {
                     lsrc = Vec3::new(hdc.dest_xz.x, 0., hdc.dest_xz.y);
                     ldst = lsrc + Vec3::new(hdc.next_move.x, 0., hdc.next_move.y);
 
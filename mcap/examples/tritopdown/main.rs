@@ -1,10 +1,8 @@
 use ::core::f32;
-use mcap::scrap as mcap;
 
-use glam::Vec2;
 use mcap::{
-    Surface, Vec3, closest_point_on_segment_v2,
-    find_floor_height_hotdog, get_face_normal,
+    Surface, Vec2, Vec3, closest_point_on_segment_v2,
+    find_floor_height_hotdog_v4, get_face_normal,
 };
 use raylib::prelude::*;
 
@@ -109,9 +107,10 @@ fn main() {
             //     src_color = Color::GREEN
             // }
 
-            let src_color = match find_floor_height_hotdog(
+            let src_color = match find_floor_height_hotdog_v4(
                 Vec3::new(p_src.x, 0., p_src.y),
                 10., // unused here
+                10.,
                 &floors,
                 radius,
             ) {
@@ -1,9 +1,4 @@
-
-// todo, remove
-pub mod scrap;
-
-// real
-pub use glam::{Vec2, Vec3A};
+pub use glam::{Affine3A, Quat, Vec2, Vec3A};
 pub use Vec3A as Vec3;
 use core::f32::{INFINITY,NEG_INFINITY};
 
@@ -17,11 +12,93 @@ pub struct Triangle {
     pub origin_offset: f32,
     pub min_y: f32,
     pub max_y: f32,
+    // the moving body this belongs to, None if static
+    pub body: Option<BodyId>,
+    pub material: Material,
 }
 
 const FLOOR_EPS: f32 = 0.01;
 
+// floors steeper than this, in radians, are slides unless their material
+// says otherwise, 60 degrees
+pub const DEFAULT_SLIDE_ANGLE: f32 = core::f32::consts::FRAC_PI_3;
+
+// ice keeps this much of the usual grip
+const ICE_TRACTION: f32 = 0.1;
+// mud drags this many times harder
+const MUD_DRAG: f32 = 3.;
+// bouncy surfaces return this much of the landing speed
+const BOUNCE_RESTITUTION: f32 = 0.8;
+// landing slower than this doesn't bounce
+const BOUNCE_MIN_SPEED: f32 = 5.;
+// pushing out of one wall can push into another in a tight corner
+const WALL_PUSH_PASSES: usize = 8;
+// casts per character step, what's left of the step after this is dropped
+const MAX_SWEEPS: u32 = 4;
+// clipped velocity leans this much off the plane, so the next cast
+// doesn't hit the same surface at 0
+const CLIP_OVERBOUNCE: f32 = 1.001;
+
+// what a triangle is made of, the defaults are plain ground
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Material {
+    // scales how quickly a controller speeds up and slows down on it
+    pub friction: f32,
+    // in radians, overrides the slide threshold for this surface
+    pub slide_angle: Option<f32>,
+    pub ice: bool,
+    pub mud: bool,
+    pub bouncy: bool,
+    // footstep tag, an id the grid's builder gives meaning to
+    pub sound: Option<u32>,
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Self {
+            friction: 1.,
+            slide_angle: None,
+            ice: false,
+            mud: false,
+            bouncy: false,
+            sound: None,
+        }
+    }
+}
+
+impl Material {
+    // share of a controller's acceleration it gets
+    pub fn traction(&self) -> f32 {
+        match self.ice {
+            true => self.friction * ICE_TRACTION,
+            false => self.friction,
+        }
+    }
+
+    // share of a controller's friction that applies
+    pub fn drag(&self) -> f32 {
+        match self.mud {
+            true => self.traction() * MUD_DRAG,
+            false => self.traction(),
+        }
+    }
+}
+
 impl Triangle {
+    // unclassified, Surface::new_with_material picks the variant from the
+    // normal, wrap it directly when that's already known, e.g. baked maps
+    pub fn new(verts: [Vec3; 3], normal: Vec3, material: Material) -> Self {
+        Self {
+            verts,
+            normal,
+            origin_offset: -normal.dot(verts[0]),
+            min_y: verts[0].y.min(verts[1].y.min(verts[2].y)),
+            max_y: verts[0].y.max(verts[1].y.max(verts[2].y)),
+            body: None,
+            material,
+        }
+    }
+
     pub fn verts(&self) -> [Vec3; 3] {
         self.verts
     }
@@ -43,15 +120,13 @@ pub enum Surface {
 
 impl Surface {
     pub fn new(verts: [Vec3; 3], normal: Vec3) -> Self {
-        let t = Triangle {
-            verts,
-            normal,
-            origin_offset: -normal.dot(verts[0]),
-            min_y: verts[0].y.min(verts[1].y.min(verts[2].y)),
-            max_y: verts[0].y.max(verts[1].y.max(verts[2].y)),
-        };
+        Self::new_with_material(verts, normal, Material::default())
+    }
 
-        let slide_deg = 60f32.to_radians().cos();
+    pub fn new_with_material(verts: [Vec3; 3], normal: Vec3, material: Material) -> Self {
+        let t = Triangle::new(verts, normal, material);
+
+        let slide_deg = material.slide_angle.unwrap_or(DEFAULT_SLIDE_ANGLE).cos();
 
         match normal.y {
             y if y.abs() < FLOOR_EPS => Surface::Wall(t),
@@ -65,6 +140,14 @@ impl Surface {
     }
 }
 
+// counter-clockwise winding faces the viewer
+pub fn get_face_normal(v1_pos: Vec3, v2_pos: Vec3, v3_pos: Vec3) -> Vec3 {
+    let edge1 = v2_pos - v1_pos;
+    let edge2 = v3_pos - v1_pos;
+
+    edge1.cross(edge2).normalize()
+}
+
 pub fn push_out_walls_2<'a>(
     pos: Vec3,
     check_height: f32,
@@ -136,6 +219,48 @@ pub fn push_out_walls_2<'a>(
     (Vec3::new(out_x, pos.y, out_z), hit, walls)
 }
 
+// push out of a single wall as an upright cylinder, pos is where the
+// cylinder is checked, the wall's front side only
+// returns (xz push, nearest point on the flattened wall)
+pub fn check_circle_tri_collision(pos: Vec3, radius: f32, wall: &Triangle) -> Option<(Vec3, Vec3)> {
+    // check if pos is in the direction of the wall's normal
+    if (pos - wall.verts[0]).dot(wall.normal) < 0. {
+        return None;
+    }
+
+    // cylinder intersection with infinite plane
+    let offset = wall.normal.dot(pos) + wall.origin_offset;
+    if offset.abs() >= radius {
+        return None;
+    }
+
+    if !(wall.min_y..=wall.max_y).contains(&pos.y) {
+        return None;
+    }
+
+    let point = flattened_cylinder_intersects_flattened_triangle(pos, radius, &wall.verts)?;
+    let push = radius - offset;
+    Some((
+        Vec3::new(wall.normal.x * push, 0., wall.normal.z * push),
+        point,
+    ))
+}
+
+// flatten on y, the first edge with its nearest xz point within radius of pos
+// (this would also be sphere_intersects_segment if we didn't flatten y)
+pub fn flattened_cylinder_intersects_flattened_triangle(
+    pos: Vec3,
+    radius: f32,
+    tri: &[Vec3; 3],
+) -> Option<Vec3> {
+    let pos_xz = pos.with_y(0.);
+
+    [(0, 1), (1, 2), (2, 0)]
+        .into_iter()
+        .map(|(i, j)| closest_point_on_segment_v3(pos_xz, tri[i].with_y(0.), tri[j].with_y(0.)))
+        .find(|edge_xz| (pos_xz - *edge_xz).length() <= radius)
+}
+
 /// Renderkit/Embree
 /// Copyright 2009-2021 Intel Corporation
 /// SPDX-License-Identifier: Apache-2.0
@@ -287,6 +412,54 @@ pub fn flattened_point_inside_flattened_triangle(
     return collision;
 }
 
+// get line segment where horizontal plane at y intersects triangle, (x, z)
+pub fn triangle_slice_at_y(verts: &[Vec3; 3], y: f32) -> Option<(Vec2, Vec2)> {
+    let mut points = Vec::new();
+
+    let edges = [
+        (&verts[0], &verts[1]),
+        (&verts[1], &verts[2]),
+        (&verts[2], &verts[0]),
+    ];
+    for (v1, v2) in edges {
+        // edge is the intersection
+        if v1.y == y && v2.y == y {
+            return Some((Vec2::new(v1.x, v1.z), Vec2::new(v2.x, v2.z)));
+        }
+
+        // skip if edge doesn't cross y
+        if (v1.y <= y && v2.y <= y) || (v1.y >= y && v2.y >= y) {
+            continue;
+        }
+
+        let t = (y - v1.y) / (v2.y - v1.y);
+        let x = v1.x + t * (v2.x - v1.x);
+        let z = v1.z + t * (v2.z - v1.z);
+        points.push(Vec2::new(x, z));
+    }
+
+    if points.len() >= 2 {
+        return Some((points[0], points[1]));
+    }
+
+    None
+}
+
+pub fn closest_point_on_segment_v3(p: Vec3, a: Vec3, b: Vec3) -> Vec3 {
+    let ab = b - a;
+    let ap = p - a;
+
+    let proj = ap.dot(ab);
+    let ab_len_sq = ab.length_squared();
+    let d = proj / ab_len_sq;
+
+    match d {
+        NEG_INFINITY..=0f32 => a,
+        1f32..=INFINITY => b,
+        d => a + ab * d,
+    }
+}
+
 pub fn closest_point_on_segment_v2(p: Vec2, a: Vec2, b: Vec2) -> Vec2 {
     let ab = b - a;
     let ap = p - a;
@@ -431,16 +604,98 @@ pub fn find_floor_height_hotdog_v4(
 }
 
 use std::ptr;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 pub const GRID_SIZE: f32 = 5.;
+// a character finds what it's touching in its own cell, which
+// reaches one cell out, so cells can't be much smaller than one
+pub const MIN_GRID_SIZE: f32 = 4.;
+
+// signed, maps are routinely centred on the origin
+pub type Cell = (i32, i32, i32);
+
+pub fn cell_of(pos: Vec3) -> Cell {
+    cell_at(pos, GRID_SIZE)
+}
+
+fn cell_at(pos: Vec3, cell_size: f32) -> Cell {
+    let fgpos = pos / cell_size;
+    (
+        fgpos.x.floor() as i32,
+        fgpos.y.floor() as i32,
+        fgpos.z.floor() as i32,
+    )
+}
 
 fn surface_verts(s: &Surface) -> &[Vec3; 3] {
-    match s {
-        Surface::Wall(t)
-        | Surface::Floor(t)
-        | Surface::Slide(t)
-        | Surface::Cieling(t) => &t.verts,
+    &s.triangle().verts
+}
+
+impl Surface {
+    pub fn triangle(&self) -> &Triangle {
+        match self {
+            Surface::Wall(t)
+            | Surface::Floor(t)
+            | Surface::Slide(t)
+            | Surface::Cieling(t) => t,
+        }
+    }
+
+    pub fn body(&self) -> Option<BodyId> {
+        self.triangle().body
+    }
+
+    fn with_body(mut self, id: BodyId) -> Self {
+        match &mut self {
+            Surface::Wall(t)
+            | Surface::Floor(t)
+            | Surface::Slide(t)
+            | Surface::Cieling(t) => t.body = Some(id),
+        }
+        self
+    }
+}
+
+// a set of surfaces moved as one after the grid is built,
+// e.g. a platform, these aren't bucketed into cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BodyId(u32);
+
+struct Body {
+    // as given to add_body
+    local: Vec<Surface>,
+    // local under transform, reclassified each move
+    // since a rotating floor can become a slide
+    surfaces: Vec<Surface>,
+    transform: Affine3A,
+    prev_transform: Affine3A,
+    min: Vec3,
+    max: Vec3,
+}
+
+impl Body {
+    fn set_transform(&mut self, id: BodyId, transform: Affine3A) {
+        self.prev_transform = self.transform;
+        self.transform = transform;
+
+        // normals go through the inverse transpose, in case of scale
+        let normal_mat = transform.matrix3.inverse().transpose();
+
+        self.min = Vec3::splat(f32::INFINITY);
+        self.max = Vec3::splat(f32::NEG_INFINITY);
+        self.surfaces.clear();
+        for s in &self.local {
+            let t = s.triangle();
+            let verts = t.verts.map(|v| transform.transform_point3a(v));
+            let normal = (normal_mat * t.normal).normalize();
+
+            for v in verts {
+                self.min = self.min.min(v);
+                self.max = self.max.max(v);
+            }
+            self.surfaces
+                .push(Surface::new_with_material(verts, normal, t.material).with_body(id));
+        }
     }
 }
 
@@ -482,13 +737,25 @@ pub struct SurfaceGrid {
     // map data (there would be an index to pointer conversion)
     // however, benchmarking showed minimal-to-no performance
     // difference on reads
-    grid: FxHashMap<(u32, u32, u32), Vec<*const Surface>>,
+    grid: FxHashMap<Cell, Vec<*const Surface>>,
+    cell_size: f32,
+    bodies: Vec<Body>,
+    // bounds of every static surface
+    min: Vec3,
+    max: Vec3,
 }
 
 impl SurfaceGrid {
     pub fn new(surfaces: Vec<Surface>) -> Self {
+        Self::with_cell_size(surfaces, GRID_SIZE)
+    }
+
+    // see the table above, what's fastest depends on the map,
+    // anything under MIN_GRID_SIZE is raised to it
+    pub fn with_cell_size(surfaces: Vec<Surface>, cell_size: f32) -> Self {
+        let cell_size = cell_size.max(MIN_GRID_SIZE);
         let surfaces: Box<[Surface]> = surfaces.into_boxed_slice();
-        let mut grid: FxHashMap<(u32, u32, u32), Vec<*const Surface>> = FxHashMap::default();
+        let mut grid: FxHashMap<Cell, Vec<*const Surface>> = FxHashMap::default();
 
         for surf in surfaces.iter() {
             let verts = surface_verts(surf);
@@ -503,27 +770,169 @@ impl SurfaceGrid {
             // +/- 1
             // registers every triangle in all neighboring grids,
             // getting cube functionality in 1 lookup - no alloc
-            let grid_min_x = (min_x / GRID_SIZE).floor() as i32 - 1;
-            let grid_max_x = (max_x / GRID_SIZE).floor() as i32 + 1;
-            let grid_min_y = (min_y / GRID_SIZE).floor() as i32 - 1 ;
-            let grid_max_y = (max_y / GRID_SIZE).floor() as i32 + 1;
-            let grid_min_z = (min_z / GRID_SIZE).floor() as i32 - 1;
-            let grid_max_z = (max_z / GRID_SIZE).floor() as i32 + 1;
+            let (grid_min_x, grid_min_y, grid_min_z) =
+                cell_at(Vec3::new(min_x, min_y, min_z), cell_size);
+            let (grid_max_x, grid_max_y, grid_max_z) =
+                cell_at(Vec3::new(max_x, max_y, max_z), cell_size);
 
             let ptr: *const Surface = ptr::from_ref(surf);
-            for x in grid_min_x..=grid_max_x {
-                for y in grid_min_y..=grid_max_y {
-                    for z in grid_min_z..=grid_max_z {
-                        grid.entry((x as u32, y as u32, z as u32))
-                            .or_default()
-                            .push(ptr);
+            for x in grid_min_x - 1..=grid_max_x + 1 {
+                for y in grid_min_y - 1..=grid_max_y + 1 {
+                    for z in grid_min_z - 1..=grid_max_z + 1 {
+                        grid.entry((x, y, z)).or_default().push(ptr);
                     }
                 }
             }
         }
 
         let all_ptrs: Vec<*const Surface> = surfaces.iter().map(ptr::from_ref).collect();
-        Self { _surfaces: surfaces, all_ptrs, grid }
+        let (min, max) = bounds_of_all(&surfaces);
+        Self {
+            _surfaces: surfaces,
+            all_ptrs,
+            grid,
+            cell_size,
+            bodies: vec![],
+            min,
+            max,
+        }
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    pub fn cell_of(&self, pos: Vec3) -> Cell {
+        cell_at(pos, self.cell_size)
+    }
+
+    // the grid with its cells as indices into surfaces, in cell order,
+    // for baking into map data, see from_cell_indices
+    pub fn cell_indices(&self) -> (Vec<Surface>, Vec<(Cell, Vec<u32>)>) {
+        let index: FxHashMap<*const Surface, u32> = self
+            .all_ptrs
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (*p, i as u32))
+            .collect();
+
+        let mut cells: Vec<(Cell, Vec<u32>)> = self
+            .grid
+            .iter()
+            .map(|(cell, ptrs)| (*cell, ptrs.iter().map(|p| index[p]).collect()))
+            .collect();
+        cells.sort_unstable_by_key(|(cell, _)| *cell);
+
+        (self._surfaces.to_vec(), cells)
+    }
+
+    // rebuilds a grid from cell_indices without re-bucketing any surfaces,
+    // None if a cell refers to a surface that isn't there
+    pub fn from_cell_indices(
+        surfaces: Vec<Surface>,
+        cells: Vec<(Cell, Vec<u32>)>,
+        cell_size: f32,
+    ) -> Option<Self> {
+        if cell_size.is_nan() || cell_size < MIN_GRID_SIZE {
+            return None;
+        }
+
+        let surfaces: Box<[Surface]> = surfaces.into_boxed_slice();
+        let all_ptrs: Vec<*const Surface> = surfaces.iter().map(ptr::from_ref).collect();
+
+        let mut grid: FxHashMap<Cell, Vec<*const Surface>> = FxHashMap::default();
+        grid.reserve(cells.len());
+        for (cell, indices) in cells {
+            let ptrs = indices
+                .iter()
+                .map(|i| all_ptrs.get(*i as usize).copied())
+                .collect::<Option<Vec<_>>>()?;
+            grid.insert(cell, ptrs);
+        }
+
+        let (min, max) = bounds_of_all(&surfaces);
+        Some(Self {
+            _surfaces: surfaces,
+            all_ptrs,
+            grid,
+            cell_size,
+            bodies: vec![],
+            min,
+            max,
+        })
+    }
+
+    // surfaces are in the body's local space, placed at the origin
+    // until the first set_body_transform
+    pub fn add_body(&mut self, surfaces: Vec<Surface>) -> BodyId {
+        let id = BodyId(self.bodies.len() as u32);
+        let mut body = Body {
+            local: surfaces,
+            surfaces: vec![],
+            transform: Affine3A::IDENTITY,
+            prev_transform: Affine3A::IDENTITY,
+            min: Vec3::ZERO,
+            max: Vec3::ZERO,
+        };
+        body.set_transform(id, Affine3A::IDENTITY);
+        self.bodies.push(body);
+        id
+    }
+
+    // call once per tick, body_displacement is relative to the previous call
+    pub fn set_body_transform(&mut self, id: BodyId, transform: Affine3A) {
+        if let Some(body) = self.bodies.get_mut(id.0 as usize) {
+            body.set_transform(id, transform);
+        }
+    }
+
+    // how far the body's last move carried a point resting on it
+    pub fn body_displacement(&self, id: BodyId, point: Vec3) -> Vec3 {
+        let Some(body) = self.bodies.get(id.0 as usize) else {
+            return Vec3::ZERO;
+        };
+        let local = body.prev_transform.inverse().transform_point3a(point);
+        body.transform.transform_point3a(local) - point
+    }
+
+    // pos's cell plus any bodies about, what a character collides with
+    pub fn surfaces_near(&self, pos: Vec3) -> Vec<&Surface> {
+        let mut surfaces = self
+            .surfaces_in_cell(self.cell_of(pos))
+            .unwrap_or_default()
+            .to_vec();
+        surfaces.extend(self.body_surfaces_near(pos, self.cell_size));
+        surfaces
+    }
+
+    // surfaces of every body whose bounds are within reach of pos
+    pub fn body_surfaces_near(&self, pos: Vec3, reach: f32) -> Vec<&Surface> {
+        let reach = Vec3::splat(reach);
+        self.body_surfaces_overlapping(pos - reach, pos + reach)
+    }
+
+    fn body_surfaces_overlapping(&self, min: Vec3, max: Vec3) -> Vec<&Surface> {
+        let mut result = Vec::new();
+        for body in &self.bodies {
+            if min.cmple(body.max).all() && max.cmpge(body.min).all() {
+                result.extend(body.surfaces.iter());
+            }
+        }
+        result
+    }
+
+    // nothing is further from p than this, casts stop there instead of
+    // walking empty cells forever
+    fn farthest_from(&self, p: Vec3) -> f32 {
+        let (mut min, mut max) = (self.min, self.max);
+        for body in &self.bodies {
+            min = min.min(body.min);
+            max = max.max(body.max);
+        }
+        if min.cmpgt(max).any() {
+            return 0.;
+        }
+        (p - min).abs().max((p - max).abs()).length()
     }
 
     pub fn all_surfaces(&self) -> Option<&[&Surface]> {
@@ -537,21 +946,18 @@ impl SurfaceGrid {
         }
     }
 
-    pub fn surfaces_in_cell(&self, cell: (u32, u32, u32)) -> Option<&[&Surface]> {
+    pub fn surfaces_in_cell(&self, cell: Cell) -> Option<&[&Surface]> {
         self.grid.get(&cell).map(|v| {
             // black magic
             unsafe { &*(v.as_slice() as *const [*const Surface] as *const [&Surface]) }
         })
     }
 
-    pub fn surfaces_in_cell_and_adjacent(&self, cell: (u32, u32, u32)) -> Vec<&Surface> {
+    pub fn surfaces_in_cell_and_adjacent(&self, cell: Cell) -> Vec<&Surface> {
         let mut result = Vec::new();
-        for dx in -1i32..=1 {
-            let Some(x) = cell.0.checked_add_signed(dx) else { continue };
-            for dy in -1i32..=1 {
-                let Some(y) = cell.1.checked_add_signed(dy) else { continue };
-                for dz in -1i32..=1 {
-                    let Some(z) = cell.2.checked_add_signed(dz) else { continue };
+        for x in cell.0 - 1..=cell.0 + 1 {
+            for y in cell.1 - 1..=cell.1 + 1 {
+                for z in cell.2 - 1..=cell.2 + 1 {
                     if let Some(surfaces) = self.surfaces_in_cell((x, y, z)) {
                         result.extend_from_slice(surfaces);
                     }
@@ -564,4 +970,2539 @@ impl SurfaceGrid {
         result.dedup_by_key(|s| *s as *const Surface);
         result
     }
-}
\ No newline at end of file
+}
+const CAST_EPS: f32 = 1e-7;
+
+#[derive(Debug, Clone, Copy)]
+pub struct CastHit<'a> {
+    pub surface: &'a Surface,
+    // along the normalized direction
+    pub distance: f32,
+    // the surface's normal for rays,
+    // for sweeps it points from the contact back at the shape
+    pub normal: Vec3,
+}
+
+impl SurfaceGrid {
+    // nearest surface along a ray, walking the cells it passes through
+    // back faces are ignored, same as push_out_walls_2
+    pub fn raycast(&self, origin: Vec3, dir: Vec3, max_dist: f32) -> Option<CastHit<'_>> {
+        let dir = dir.normalize_or_zero();
+        if dir == Vec3::ZERO {
+            return None;
+        }
+        // also takes care of an infinite or NaN max_dist
+        let max_dist = max_dist.min(self.farthest_from(origin));
+
+        let mut best = None;
+
+        let end = origin + dir * max_dist;
+        for s in self.body_surfaces_overlapping(origin.min(end), origin.max(end)) {
+            keep_closer(&mut best, s, ray_surface(origin, dir, s), max_dist);
+        }
+
+        let mut tested = FxHashSet::default();
+        walk_cells(origin, dir, max_dist, self.cell_size, |cell, t_exit| {
+            for s in self.surfaces_in_cell(cell).unwrap_or_default() {
+                if tested.insert(ptr::from_ref(*s)) {
+                    keep_closer(&mut best, s, ray_surface(origin, dir, s), max_dist);
+                }
+            }
+            // nothing in a later cell can be closer
+            best.is_some_and(|h| h.distance <= t_exit)
+        });
+
+        best
+    }
+
+    pub fn sphere_cast(
+        &self,
+        center: Vec3,
+        radius: f32,
+        dir: Vec3,
+        max_dist: f32,
+    ) -> Option<CastHit<'_>> {
+        self.capsule_cast(center, center, radius, dir, max_dist)
+    }
+
+    // capsule around the segment a-b, first contact moving along dir
+    pub fn capsule_cast(
+        &self,
+        a: Vec3,
+        b: Vec3,
+        radius: f32,
+        dir: Vec3,
+        max_dist: f32,
+    ) -> Option<CastHit<'_>> {
+        let dir = dir.normalize_or_zero();
+        if dir == Vec3::ZERO {
+            return None;
+        }
+        let reach = self.farthest_from(a).max(self.farthest_from(b)) + radius;
+        let max_dist = max_dist.min(reach);
+
+        let end_min = a.min(b).min(a.min(b) + dir * max_dist) - Vec3::splat(radius);
+        let end_max = a.max(b).max(a.max(b) + dir * max_dist) + Vec3::splat(radius);
+        let mut candidates = self.body_surfaces_overlapping(end_min, end_max);
+
+        if radius > self.cell_size / 2. {
+            // past what the cell padding covers
+            candidates.extend(self.all_surfaces().unwrap_or_default());
+        } else {
+            // every surface within a cell of a point is registered in the
+            // point's cell, so points along the segment at most a cell apart
+            // see everything within radius of the capsule
+            let n = ((b - a).length() / self.cell_size).ceil().max(1.) as usize;
+            let mut seen = FxHashSet::default();
+            for i in 0..=n {
+                let p = a.lerp(b, i as f32 / n as f32);
+                walk_cells(p, dir, max_dist, self.cell_size, |cell, _| {
+                    for s in self.surfaces_in_cell(cell).unwrap_or_default() {
+                        if seen.insert(ptr::from_ref(*s)) {
+                            candidates.push(*s);
+                        }
+                    }
+                    false
+                });
+            }
+        }
+
+        let mut best = None;
+        for s in candidates {
+            let hit = sweep_capsule_triangle(a, b, radius, dir, max_dist, s.triangle());
+            keep_closer(&mut best, s, hit, max_dist);
+        }
+        best
+    }
+}
+
+// what a character controller needs from level collision,
+// answered by both SurfaceGrid and SurfaceBvh
+pub trait SurfaceQuery {
+    // everything a character at pos could be touching, and then some
+    fn surfaces_near(&self, pos: Vec3) -> Vec<&Surface>;
+
+    // nearest surface along a ray, back faces are ignored
+    fn raycast(&self, origin: Vec3, dir: Vec3, max_dist: f32) -> Option<CastHit<'_>>;
+
+    // capsule around the segment a-b, first contact moving along dir
+    fn capsule_cast(
+        &self,
+        a: Vec3,
+        b: Vec3,
+        radius: f32,
+        dir: Vec3,
+        max_dist: f32,
+    ) -> Option<CastHit<'_>>;
+
+    fn sphere_cast(
+        &self,
+        center: Vec3,
+        radius: f32,
+        dir: Vec3,
+        max_dist: f32,
+    ) -> Option<CastHit<'_>> {
+        self.capsule_cast(center, center, radius, dir, max_dist)
+    }
+
+    // see SurfaceGrid::body_displacement, nothing moves by default
+    fn body_displacement(&self, _id: BodyId, _point: Vec3) -> Vec3 {
+        Vec3::ZERO
+    }
+}
+
+impl SurfaceQuery for SurfaceGrid {
+    fn surfaces_near(&self, pos: Vec3) -> Vec<&Surface> {
+        SurfaceGrid::surfaces_near(self, pos)
+    }
+
+    fn raycast(&self, origin: Vec3, dir: Vec3, max_dist: f32) -> Option<CastHit<'_>> {
+        SurfaceGrid::raycast(self, origin, dir, max_dist)
+    }
+
+    fn capsule_cast(
+        &self,
+        a: Vec3,
+        b: Vec3,
+        radius: f32,
+        dir: Vec3,
+        max_dist: f32,
+    ) -> Option<CastHit<'_>> {
+        SurfaceGrid::capsule_cast(self, a, b, radius, dir, max_dist)
+    }
+
+    fn body_displacement(&self, id: BodyId, point: Vec3) -> Vec3 {
+        SurfaceGrid::body_displacement(self, id, point)
+    }
+}
+
+// most surfaces a leaf holds before it's split
+const BVH_LEAF_SIZE: usize = 4;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct BvhNode {
+    min: Vec3,
+    max: Vec3,
+    // leaves hold surfaces[first..first + count], otherwise
+    // count is 0 and the children are nodes[first] and nodes[first + 1]
+    first: u32,
+    count: u32,
+}
+
+// static surfaces in a bounding volume hierarchy, where SurfaceGrid
+// registers a big floor in every cell it's near, this holds it once
+// no moving bodies, see SurfaceGrid::add_body
+pub struct SurfaceBvh {
+    surfaces: Vec<Surface>,
+    nodes: Vec<BvhNode>,
+}
+
+fn surface_bounds(s: &Surface) -> (Vec3, Vec3) {
+    let [a, b, c] = s.triangle().verts;
+    (a.min(b).min(c), a.max(b).max(c))
+}
+
+// inside out for no surfaces
+fn bounds_of_all(surfaces: &[Surface]) -> (Vec3, Vec3) {
+    surfaces.iter().map(surface_bounds).fold(
+        (Vec3::splat(f32::INFINITY), Vec3::splat(f32::NEG_INFINITY)),
+        |(min, max), (a, b)| (min.min(a), max.max(b)),
+    )
+}
+
+fn boxes_overlap(min_a: Vec3, max_a: Vec3, min_b: Vec3, max_b: Vec3) -> bool {
+    min_a.cmple(max_b).all() && max_a.cmpge(min_b).all()
+}
+
+// slab test, whether the ray enters the box before reach
+fn ray_hits_box(origin: Vec3, dir: Vec3, reach: f32, min: Vec3, max: Vec3) -> bool {
+    let (mut t_enter, mut t_exit) = (0f32, reach);
+    for i in 0..3 {
+        if dir[i] == 0. {
+            if origin[i] < min[i] || origin[i] > max[i] {
+                return false;
+            }
+            continue;
+        }
+        let t0 = (min[i] - origin[i]) / dir[i];
+        let t1 = (max[i] - origin[i]) / dir[i];
+        t_enter = t_enter.max(t0.min(t1));
+        t_exit = t_exit.min(t0.max(t1));
+        if t_enter > t_exit {
+            return false;
+        }
+    }
+    true
+}
+
+impl SurfaceBvh {
+    pub fn new(mut surfaces: Vec<Surface>) -> Self {
+        let mut nodes = vec![];
+        if !surfaces.is_empty() {
+            nodes.push(BvhNode::default());
+            Self::build(&mut nodes, &mut surfaces, 0, 0);
+        }
+        Self { surfaces, nodes }
+    }
+
+    // splits at the median centroid along the longest axis
+    fn build(nodes: &mut Vec<BvhNode>, surfaces: &mut [Surface], node: usize, first: usize) {
+        let mut min = Vec3::splat(f32::INFINITY);
+        let mut max = Vec3::splat(f32::NEG_INFINITY);
+        let mut centroid_min = min;
+        let mut centroid_max = max;
+        for s in surfaces.iter() {
+            let (lo, hi) = surface_bounds(s);
+            min = min.min(lo);
+            max = max.max(hi);
+            centroid_min = centroid_min.min((lo + hi) / 2.);
+            centroid_max = centroid_max.max((lo + hi) / 2.);
+        }
+
+        nodes[node] = BvhNode {
+            min,
+            max,
+            first: first as u32,
+            count: surfaces.len() as u32,
+        };
+        if surfaces.len() <= BVH_LEAF_SIZE {
+            return;
+        }
+
+        let extent = centroid_max - centroid_min;
+        let axis = match extent.max_element() {
+            e if e == extent.x => 0,
+            e if e == extent.y => 1,
+            _ => 2,
+        };
+        let mid = surfaces.len() / 2;
+        surfaces.select_nth_unstable_by(mid, |a, b| {
+            let (a_lo, a_hi) = surface_bounds(a);
+            let (b_lo, b_hi) = surface_bounds(b);
+            (a_lo[axis] + a_hi[axis]).total_cmp(&(b_lo[axis] + b_hi[axis]))
+        });
+
+        let left = nodes.len();
+        nodes.extend([BvhNode::default(); 2]);
+        nodes[node].first = left as u32;
+        nodes[node].count = 0;
+
+        let (l, r) = surfaces.split_at_mut(mid);
+        Self::build(nodes, l, left, first);
+        Self::build(nodes, r, left + 1, first + mid);
+    }
+
+    pub fn surfaces(&self) -> &[Surface] {
+        &self.surfaces
+    }
+
+    fn leaf(&self, node: &BvhNode) -> &[Surface] {
+        &self.surfaces[node.first as usize..(node.first + node.count) as usize]
+    }
+
+    // every surface whose bounds touch the box
+    pub fn surfaces_overlapping(&self, min: Vec3, max: Vec3) -> Vec<&Surface> {
+        let mut result = vec![];
+        let mut stack = vec![];
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+        while let Some(i) = stack.pop() {
+            let node = &self.nodes[i];
+            if !boxes_overlap(min, max, node.min, node.max) {
+                continue;
+            }
+            if node.count == 0 {
+                stack.extend([node.first as usize, node.first as usize + 1]);
+                continue;
+            }
+            for s in self.leaf(node) {
+                let (lo, hi) = surface_bounds(s);
+                if boxes_overlap(min, max, lo, hi) {
+                    result.push(s);
+                }
+            }
+        }
+        result
+    }
+}
+
+impl SurfaceQuery for SurfaceBvh {
+    // as far as a default grid cell reaches
+    fn surfaces_near(&self, pos: Vec3) -> Vec<&Surface> {
+        let reach = Vec3::splat(GRID_SIZE);
+        self.surfaces_overlapping(pos - reach, pos + reach)
+    }
+
+    fn raycast(&self, origin: Vec3, dir: Vec3, max_dist: f32) -> Option<CastHit<'_>> {
+        let dir = dir.normalize_or_zero();
+        if dir == Vec3::ZERO {
+            return None;
+        }
+
+        let mut best: Option<CastHit> = None;
+        let mut stack = vec![];
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+        while let Some(i) = stack.pop() {
+            let node = &self.nodes[i];
+            // nothing past the closest hit so far can be closer
+            let reach = best.map_or(max_dist, |h| h.distance);
+            if !ray_hits_box(origin, dir, reach, node.min, node.max) {
+                continue;
+            }
+            if node.count == 0 {
+                stack.extend([node.first as usize, node.first as usize + 1]);
+                continue;
+            }
+            for s in self.leaf(node) {
+                keep_closer(&mut best, s, ray_surface(origin, dir, s), max_dist);
+            }
+        }
+        best
+    }
+
+    fn capsule_cast(
+        &self,
+        a: Vec3,
+        b: Vec3,
+        radius: f32,
+        dir: Vec3,
+        max_dist: f32,
+    ) -> Option<CastHit<'_>> {
+        let dir = dir.normalize_or_zero();
+        if dir == Vec3::ZERO {
+            return None;
+        }
+
+        let end_min = a.min(b).min(a.min(b) + dir * max_dist) - Vec3::splat(radius);
+        let end_max = a.max(b).max(a.max(b) + dir * max_dist) + Vec3::splat(radius);
+
+        let mut best = None;
+        for s in self.surfaces_overlapping(end_min, end_max) {
+            let hit = sweep_capsule_triangle(a, b, radius, dir, max_dist, s.triangle());
+            keep_closer(&mut best, s, hit, max_dist);
+        }
+        best
+    }
+}
+
+fn ray_surface(origin: Vec3, dir: Vec3, s: &Surface) -> Option<(f32, Vec3)> {
+    let tri = s.triangle();
+    if tri.normal.dot(dir) >= 0. {
+        return None;
+    }
+    ray_triangle(origin, dir, &tri.verts).map(|t| (t, tri.normal))
+}
+
+fn keep_closer<'a>(
+    best: &mut Option<CastHit<'a>>,
+    surface: &'a Surface,
+    hit: Option<(f32, Vec3)>,
+    max_dist: f32,
+) {
+    let Some((distance, normal)) = hit else {
+        return;
+    };
+    if distance <= max_dist && best.is_none_or(|b| distance < b.distance) {
+        *best = Some(CastHit {
+            surface,
+            distance,
+            normal,
+        });
+    }
+}
+
+// grid DDA, Amanatides & Woo
+// visits each cell a ray passes through in order with the distance it
+// leaves the cell at, until visit returns true or max_dist is passed
+fn walk_cells(
+    origin: Vec3,
+    dir: Vec3,
+    max_dist: f32,
+    cell_size: f32,
+    mut visit: impl FnMut(Cell, f32) -> bool,
+) {
+    let start = cell_at(origin, cell_size);
+    let mut cell = [start.0, start.1, start.2];
+    let mut step = [0i32; 3];
+    let mut t_max = [f32::INFINITY; 3];
+    let mut t_delta = [f32::INFINITY; 3];
+
+    for i in 0..3 {
+        if dir[i] > 0. {
+            step[i] = 1;
+            t_max[i] = ((cell[i] + 1) as f32 * cell_size - origin[i]) / dir[i];
+            t_delta[i] = cell_size / dir[i];
+        } else if dir[i] < 0. {
+            step[i] = -1;
+            t_max[i] = (cell[i] as f32 * cell_size - origin[i]) / dir[i];
+            t_delta[i] = -cell_size / dir[i];
+        }
+    }
+
+    loop {
+        let axis = if t_max[0] <= t_max[1] && t_max[0] <= t_max[2] {
+            0
+        } else if t_max[1] <= t_max[2] {
+            1
+        } else {
+            2
+        };
+        let t_exit = t_max[axis];
+
+        if visit((cell[0], cell[1], cell[2]), t_exit) || t_exit > max_dist {
+            return;
+        }
+
+        cell[axis] += step[axis];
+        t_max[axis] += t_delta[axis];
+    }
+}
+
+// Moller-Trumbore, both sides, distance along dir
+pub fn ray_triangle(origin: Vec3, dir: Vec3, verts: &[Vec3; 3]) -> Option<f32> {
+    let e1 = verts[1] - verts[0];
+    let e2 = verts[2] - verts[0];
+    let p = dir.cross(e2);
+    let det = e1.dot(p);
+    if det.abs() < CAST_EPS {
+        return None;
+    }
+
+    let inv = 1. / det;
+    let s = origin - verts[0];
+    let u = s.dot(p) * inv;
+    if !(0. ..=1.).contains(&u) {
+        return None;
+    }
+    let q = s.cross(e1);
+    let v = dir.dot(q) * inv;
+    if v < 0. || u + v > 1. {
+        return None;
+    }
+
+    let t = e2.dot(q) * inv;
+    (t >= 0.).then_some(t)
+}
+
+// the capsule around segment a-b moving along a normalized dir, the first
+// distance it touches tri at and the contact normal, 0 if already touching
+//
+// this is a ray from a against the triangle swept by a - b (a prism) grown
+// by radius, whose surface is made of the prism's faces pushed out, a
+// cylinder around each edge and a sphere on each corner
+pub fn sweep_capsule_triangle(
+    a: Vec3,
+    b: Vec3,
+    radius: f32,
+    dir: Vec3,
+    max_dist: f32,
+    tri: &Triangle,
+) -> Option<(f32, Vec3)> {
+    // only surfaces being moved into, same as push_out_walls_2
+    if tri.normal.dot(dir) >= 0. {
+        return None;
+    }
+
+    let v = tri.verts;
+    let (on_seg, on_tri) = closest_segment_triangle(a, b, &v);
+    if on_seg.distance_squared(on_tri) < radius * radius {
+        return Some((0., (on_seg - on_tri).normalize_or(tri.normal)));
+    }
+
+    let e = a - b;
+    let swept = e.length_squared() > CAST_EPS;
+    let w = [v[0] + e, v[1] + e, v[2] + e];
+
+    let mut best: Option<(f32, Vec3)> = None;
+    let mut consider = |hit: Option<(f32, Vec3)>| {
+        if let Some((t, n)) = hit
+            && t <= max_dist
+            && best.is_none_or(|(bt, _)| t < bt)
+        {
+            best = Some((t, n));
+        }
+    };
+
+    consider(ray_rounded_polygon(a, dir, &v, radius));
+    for (i, j) in [(0, 1), (1, 2), (2, 0)] {
+        consider(ray_cylinder(a, dir, v[i], v[j], radius));
+    }
+    for p in v {
+        consider(ray_sphere(a, dir, p, radius));
+    }
+
+    if swept {
+        consider(ray_rounded_polygon(a, dir, &w, radius));
+        for (i, j) in [(0, 1), (1, 2), (2, 0)] {
+            consider(ray_rounded_polygon(
+                a,
+                dir,
+                &[v[i], v[j], w[j], w[i]],
+                radius,
+            ));
+            consider(ray_cylinder(a, dir, w[i], w[j], radius));
+        }
+        for i in 0..3 {
+            consider(ray_cylinder(a, dir, v[i], w[i], radius));
+            consider(ray_sphere(a, dir, w[i], radius));
+        }
+    }
+
+    best
+}
+
+// convex planar polygon pushed out by radius towards the ray
+fn ray_rounded_polygon(origin: Vec3, dir: Vec3, poly: &[Vec3], radius: f32) -> Option<(f32, Vec3)> {
+    let n = (poly[1] - poly[0])
+        .cross(poly[2] - poly[0])
+        .normalize_or_zero();
+    if n == Vec3::ZERO {
+        return None;
+    }
+
+    let facing = n.dot(dir);
+    if facing.abs() < CAST_EPS {
+        return None;
+    }
+    let (n, facing) = if facing < 0. {
+        (n, facing)
+    } else {
+        (-n, -facing)
+    };
+
+    // starting closer than radius can't touch the face first
+    let dist = n.dot(origin - poly[0]);
+    if dist < radius {
+        return None;
+    }
+
+    let t = (dist - radius) / -facing;
+    let on_plane = origin + dir * t - n * radius;
+    point_in_convex_polygon(on_plane, poly, n).then_some((t, n))
+}
+
+fn point_in_convex_polygon(p: Vec3, poly: &[Vec3], n: Vec3) -> bool {
+    let mut sign = 0.;
+    for i in 0..poly.len() {
+        let edge = poly[(i + 1) % poly.len()] - poly[i];
+        let side = edge.cross(p - poly[i]).dot(n);
+        if side.abs() < CAST_EPS {
+            continue;
+        }
+        if sign == 0. {
+            sign = side.signum();
+        } else if side.signum() != sign {
+            return false;
+        }
+    }
+    true
+}
+
+// cylinder around segment p-q, entering from outside only
+fn ray_cylinder(origin: Vec3, dir: Vec3, p: Vec3, q: Vec3, radius: f32) -> Option<(f32, Vec3)> {
+    let axis = q - p;
+    let axis_len_sq = axis.length_squared();
+    if axis_len_sq < CAST_EPS {
+        return None;
+    }
+
+    let m = origin - p;
+    // perpendicular to the axis
+    let dp = dir - axis * (dir.dot(axis) / axis_len_sq);
+    let mp = m - axis * (m.dot(axis) / axis_len_sq);
+
+    let a = dp.length_squared();
+    if a < CAST_EPS {
+        return None;
+    }
+    let b = mp.dot(dp);
+    let c = mp.length_squared() - radius * radius;
+    if c < 0. {
+        return None;
+    }
+    let disc = b * b - a * c;
+    if disc < 0. {
+        return None;
+    }
+
+    let t = (-b - disc.sqrt()) / a;
+    if t < 0. {
+        return None;
+    }
+
+    let hit = origin + dir * t;
+    let s = (hit - p).dot(axis) / axis_len_sq;
+    if !(0. ..=1.).contains(&s) {
+        return None;
+    }
+    Some((t, (hit - (p + axis * s)).normalize()))
+}
+
+// entering from outside only, dir normalized
+fn ray_sphere(origin: Vec3, dir: Vec3, center: Vec3, radius: f32) -> Option<(f32, Vec3)> {
+    let m = origin - center;
+    let b = m.dot(dir);
+    let c = m.length_squared() - radius * radius;
+    if c < 0. {
+        return None;
+    }
+    let disc = b * b - c;
+    if disc < 0. {
+        return None;
+    }
+
+    let t = -b - disc.sqrt();
+    if t < 0. {
+        return None;
+    }
+    Some((t, (origin + dir * t - center) / radius))
+}
+
+/// cute_c2, c2RaytoCircle
+/// github.com/RandyGaul/cute_headers, public domain
+///
+/// dir normalized, max_t a distance along it, returns (t, normal)
+pub fn ray_circle_2d(
+    origin: Vec2,
+    dir: Vec2,
+    max_t: f32,
+    center: Vec2,
+    radius: f32,
+) -> Option<(f32, Vec2)> {
+    let m = origin - center;
+    let c = m.dot(m) - radius * radius;
+    let b = m.dot(dir);
+    let disc = b * b - c;
+    if disc < 0. {
+        return None;
+    }
+
+    let t = -b - disc.sqrt();
+    if !(0. ..=max_t).contains(&t) {
+        return None;
+    }
+    Some((t, (origin + dir * t - center).normalize()))
+}
+
+/// cute_c2, c2RaytoCapsule
+/// github.com/RandyGaul/cute_headers, public domain
+///
+/// a ray starting inside hits at t = 0, pointing out from the axis
+pub fn ray_capsule_2d(
+    origin: Vec2,
+    dir: Vec2,
+    max_t: f32,
+    a: Vec2,
+    b: Vec2,
+    radius: f32,
+) -> Option<(f32, Vec2)> {
+    if a == b {
+        return ray_circle_2d(origin, dir, max_t, a, radius);
+    }
+
+    // capsule space, a at the origin and b up the y axis
+    let my = (b - a).normalize();
+    let mx = my.perp();
+    let to_local = |v: Vec2| Vec2::new(mx.dot(v), my.dot(v));
+
+    let y_bb = to_local(b - a);
+    let y_ap = to_local(origin - a);
+    let y_ae = y_ap + to_local(dir) * max_t;
+
+    let inside_prism = y_ap.x.abs() <= radius && (0. ..=y_bb.y).contains(&y_ap.y);
+    if inside_prism || origin.distance(a) < radius || origin.distance(b) < radius {
+        let axis = closest_point_on_segment_v2(origin, a, b);
+        return Some((0., (origin - axis).normalize_or(mx)));
+    }
+
+    if y_ae.x * y_ap.x >= 0. && y_ae.x.abs().min(y_ap.x.abs()) >= radius {
+        return None;
+    }
+
+    // ray starts beside the prism, must hit one of the semi-circles
+    if y_ap.x.abs() < radius {
+        let end = if y_ap.y < 0. { a } else { b };
+        return ray_circle_2d(origin, dir, max_t, end, radius);
+    }
+
+    // hit the prism's side, or past it the end circle
+    let c = radius.copysign(y_ap.x);
+    let t = (c - y_ap.x) / (y_ae.x - y_ap.x);
+    let y = y_ap.y + (y_ae.y - y_ap.y) * t;
+    if y <= 0. {
+        return ray_circle_2d(origin, dir, max_t, a, radius);
+    }
+    if y >= y_bb.y {
+        return ray_circle_2d(origin, dir, max_t, b, radius);
+    }
+    Some((t * max_t, mx * c.signum()))
+}
+
+/// Real-Time Collision Detection, Christer Ericson, 5.1.9
+pub fn closest_points_segments(p1: Vec3, q1: Vec3, p2: Vec3, q2: Vec3) -> (Vec3, Vec3) {
+    let d1 = q1 - p1;
+    let d2 = q2 - p2;
+    let r = p1 - p2;
+    let a = d1.length_squared();
+    let e = d2.length_squared();
+    let f = d2.dot(r);
+
+    if a <= CAST_EPS && e <= CAST_EPS {
+        return (p1, p2);
+    }
+
+    let (s, t) = if a <= CAST_EPS {
+        (0., (f / e).clamp(0., 1.))
+    } else {
+        let c = d1.dot(r);
+        if e <= CAST_EPS {
+            ((-c / a).clamp(0., 1.), 0.)
+        } else {
+            let b = d1.dot(d2);
+            let denom = a * e - b * b;
+            let s = if denom != 0. {
+                ((b * f - c * e) / denom).clamp(0., 1.)
+            } else {
+                0.
+            };
+            let t = (b * s + f) / e;
+            if t < 0. {
+                ((-c / a).clamp(0., 1.), 0.)
+            } else if t > 1. {
+                (((b - c) / a).clamp(0., 1.), 1.)
+            } else {
+                (s, t)
+            }
+        }
+    };
+
+    (p1 + d1 * s, p2 + d2 * t)
+}
+
+// closest point on segment a-b and on the triangle
+pub fn closest_segment_triangle(a: Vec3, b: Vec3, tri: &[Vec3; 3]) -> (Vec3, Vec3) {
+    let ab = b - a;
+    let len = ab.length();
+    if len > CAST_EPS
+        && let Some(t) = ray_triangle(a, ab / len, tri)
+        && t <= len
+    {
+        let p = a + ab * (t / len);
+        return (p, p);
+    }
+
+    let mut best = (a, closest_point_triangle(a, tri));
+    let mut consider = |pair: (Vec3, Vec3)| {
+        if pair.0.distance_squared(pair.1) < best.0.distance_squared(best.1) {
+            best = pair;
+        }
+    };
+    consider((b, closest_point_triangle(b, tri)));
+    for (i, j) in [(0, 1), (1, 2), (2, 0)] {
+        consider(closest_points_segments(a, b, tri[i], tri[j]));
+    }
+    best
+}
+
+// a capsule-ish character standing on its feet (position), pushed out of
+// walls at chest height, snapped onto floors and stopped by ceilings
+#[derive(Debug, Clone)]
+pub struct CharacterController {
+    // feet
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub height: f32,
+    pub radius: f32,
+    pub snap_up: f32,
+    pub snap_down: f32,
+    // floors steeper than this, in radians, are slid down,
+    // unless the floor's material has its own
+    pub slide_angle: f32,
+    pub accel: f32,
+    pub air_accel: f32,
+    pub friction: f32,
+    pub air_friction: f32,
+    pub jump_speed: f32,
+    pub gravity: f32,
+    pub terminal_velocity: f32,
+    pub on_ground: bool,
+    // the moving body stood on, it carries the controller along
+    pub floor_body: Option<BodyId>,
+    // that body's velocity, kept when stepping or jumping off
+    carry_velocity: Vec3,
+    // what's underfoot, scales ground accel and friction
+    floor_material: Material,
+}
+
+// what a step touched
+#[derive(Debug, Default)]
+pub struct StepReport {
+    pub walls: Vec<Triangle>,
+    // the last floor or slide stood on
+    pub floor: Option<Surface>,
+    pub ceiling: Option<Triangle>,
+    // downward speed when landing, None if it didn't land
+    pub landed_speed: Option<f32>,
+    pub hit_wall: bool,
+    // capsule casts made, at most MAX_SWEEPS
+    pub sweeps: u32,
+}
+
+impl CharacterController {
+    // tuned for the player, 4x regular gravity and terminal velocity
+    pub fn new(position: Vec3, height: f32, radius: f32) -> Self {
+        Self {
+            position,
+            velocity: Vec3::ZERO,
+            height,
+            radius,
+            snap_up: 1.,
+            snap_down: 0.5,
+            slide_angle: DEFAULT_SLIDE_ANGLE,
+            accel: 100.,
+            air_accel: 80.,
+            friction: 10.,
+            air_friction: 9.,
+            jump_speed: 15.,
+            gravity: -36.,
+            terminal_velocity: -216.,
+            on_ground: true,
+            floor_body: None,
+            carry_velocity: Vec3::ZERO,
+            floor_material: Material::default(),
+        }
+    }
+
+    // walls are pushed out of at this height
+    pub fn chest_height(&self) -> f32 {
+        self.height - self.radius
+    }
+
+    // the capsule step collides with level geometry, for ColliderSet
+    pub fn collider_shape(&self) -> ColliderShape {
+        ColliderShape::Capsule {
+            a: self.position + Vec3::new(0., self.radius, 0.),
+            b: self.position + Vec3::new(0., self.chest_height(), 0.),
+            radius: self.radius,
+        }
+    }
+
+    fn walkable(&self, tri: &Triangle) -> bool {
+        let angle = tri.material.slide_angle.unwrap_or(self.slide_angle);
+        tri.normal.y >= angle.cos()
+    }
+
+    // at rest, e.g. after a teleport
+    pub fn stop(&mut self) {
+        self.velocity = Vec3::ZERO;
+        self.on_ground = true;
+        self.floor_body = None;
+        self.carry_velocity = Vec3::ZERO;
+    }
+
+    // wish is the horizontal direction to move in, its length scales
+    // acceleration, e.g. 1.5 for sprinting
+    pub fn step(
+        &mut self,
+        dt: f32,
+        wish: Vec3,
+        jump: bool,
+        grid: &impl SurfaceQuery,
+    ) -> StepReport {
+        let mut report = StepReport::default();
+
+        // ride along with whatever body is underfoot
+        let carried = match (self.on_ground, self.floor_body) {
+            (true, Some(body)) if dt > 0. => {
+                let d = grid.body_displacement(body, self.position);
+                self.position += d;
+                self.carry_velocity = d / dt;
+                true
+            }
+            _ => false,
+        };
+
+        let (accel, friction) = match self.on_ground {
+            true => (
+                self.accel * self.floor_material.traction(),
+                self.friction * self.floor_material.drag(),
+            ),
+            false => (self.air_accel, self.air_friction),
+        };
+        let friction_factor = 1.0 - (friction * dt).min(1.0);
+        self.velocity *= Vec3::new(friction_factor, 1., friction_factor);
+        self.velocity += Vec3::new(wish.x, 0., wish.z) * accel * dt;
+
+        if jump && self.on_ground {
+            self.velocity.y = self.jump_speed;
+            self.on_ground = false;
+        }
+
+        let chest_height = self.chest_height();
+        // how far a move may end up inside a surface, push_out_walls_2 and
+        // the floor snap resolve anything shallower
+        let max_overlap = self.radius / 10.;
+        let cast_radius = self.radius - SKIN_FACTOR * 2.;
+
+        // move up to the first contact, resolve it, then carry on with the
+        // time left sliding along what was hit
+        let mut remaining = dt;
+        let mut planes = vec![];
+        while remaining > 0. && report.sweeps < MAX_SWEEPS {
+            report.sweeps += 1;
+            let motion = clip_to_planes(self.velocity, &planes);
+            let desired_move = motion * remaining;
+            let len = desired_move.length();
+            let a = self.position + Vec3::new(0., self.radius, 0.);
+            let b = self.position + Vec3::new(0., chest_height, 0.);
+
+            let fd = match grid.capsule_cast(a, b, cast_radius, desired_move, len) {
+                Some(hit) if hit.distance + max_overlap < len => {
+                    planes.push(hit.normal);
+                    remaining * (hit.distance + max_overlap) / len
+                }
+                _ => remaining,
+            };
+            remaining -= fd;
+
+            let mut pos = self.position + motion * fd;
+            let surfaces = grid.surfaces_near(pos);
+
+            for _ in 0..WALL_PUSH_PASSES {
+                let (hit, walls);
+                (pos, hit, walls) = push_out_walls_2(pos, chest_height, self.radius, &surfaces);
+                report.walls.extend(walls.into_iter().copied());
+                report.hit_wall |= hit;
+                if !hit {
+                    break;
+                }
+            }
+
+            // snap down only when on ground
+            let snap_down = match self.on_ground {
+                true => self.snap_down,
+                false => 0.,
+            };
+            // when the center is half a radius off a ledge, start falling
+            match find_floor_height_hotdog_v4(
+                pos,
+                self.snap_up,
+                snap_down,
+                &surfaces,
+                self.radius / 2.,
+            ) {
+                Some((floor, y)) if self.walkable(floor.triangle()) => {
+                    pos.y = y;
+                    let material = floor.triangle().material;
+                    let landing_speed = -self.velocity.y;
+                    if !self.on_ground && material.bouncy && landing_speed > BOUNCE_MIN_SPEED {
+                        // bounced, not landed
+                        self.velocity.y = landing_speed * BOUNCE_RESTITUTION;
+                        self.floor_body = None;
+                    } else {
+                        if !self.on_ground {
+                            report.landed_speed = Some(landing_speed);
+                        }
+                        self.velocity.y = self.velocity.y.max(0.0);
+                        self.on_ground = true;
+                        self.floor_body = floor.body();
+                        self.floor_material = material;
+                    }
+                    report.floor = Some(floor);
+                }
+                Some((slide, y)) => {
+                    pos.y = y;
+                    let n = slide.triangle().normal;
+                    let g = Vec3::new(0.0, self.gravity, 0.0);
+
+                    // remove velocity into the slope, then slide down it
+                    self.velocity -= n * self.velocity.dot(n);
+                    self.velocity += (g - n * g.dot(n)) * fd;
+
+                    self.on_ground = false;
+                    self.floor_body = None;
+                    report.floor = Some(slide);
+                }
+                None => {
+                    // falling
+                    self.velocity.y =
+                        (self.velocity.y + self.gravity * fd).max(self.terminal_velocity);
+                    self.on_ground = false;
+                    self.floor_body = None;
+                }
+            }
+
+            if let Some((Surface::Cieling(ciel), y)) = find_ciel_height_hotdog_v3(
+                pos,
+                chest_height,
+                self.height - chest_height,
+                &surfaces,
+                self.radius / 2.,
+            ) {
+                pos.y = y - self.height;
+                self.velocity.y = self.velocity.y.min(0.0);
+                report.ceiling = Some(ciel);
+            }
+
+            self.position = pos;
+        }
+
+        // stepped or jumped off, keep the body's momentum
+        if carried && !self.on_ground {
+            self.velocity += self.carry_velocity;
+        }
+
+        report
+    }
+
+    // moved by offset as far as the level allows, sliding along whatever's
+    // in the way, for being pushed apart from another collider
+    pub fn shove(&mut self, offset: Vec3, grid: &impl SurfaceQuery) {
+        let cast_radius = self.radius - SKIN_FACTOR * 2.;
+        let mut remaining = offset;
+        let mut planes = vec![];
+        for _ in 0..MAX_SWEEPS {
+            let motion = clip_to_planes(remaining, &planes);
+            let len = motion.length();
+            if len <= CAST_EPS {
+                return;
+            }
+            let a = self.position + Vec3::new(0., self.radius, 0.);
+            let b = self.position + Vec3::new(0., self.chest_height(), 0.);
+            let Some(hit) = grid.capsule_cast(a, b, cast_radius, motion, len) else {
+                self.position += motion;
+                return;
+            };
+            self.position += motion * (hit.distance / len);
+            remaining = motion * (1. - hit.distance / len);
+            planes.push(hit.normal);
+        }
+    }
+}
+
+// v without what heads into each plane, along the crease of the last two
+// if that still heads into one
+fn clip_to_planes(v: Vec3, planes: &[Vec3]) -> Vec3 {
+    let mut out = v;
+    for n in planes {
+        let into = out.dot(*n);
+        if into < 0. {
+            out -= *n * into * CLIP_OVERBOUNCE;
+        }
+    }
+
+    if let [.., n1, n2] = planes
+        && planes.iter().any(|n| out.dot(*n) < 0.)
+    {
+        let crease = n1.cross(*n2).normalize_or_zero();
+        out = crease * crease.dot(v);
+    }
+    out
+}
+
+// how many halvings to find the closest point of a segment to a shape,
+// plenty for trigger-sized things
+const SEGMENT_SEARCH_STEPS: u32 = 32;
+
+// a convex region, for triggers, water, kill zones, ladders and the like
+#[derive(Debug, Clone)]
+pub enum Shape {
+    // half extents along the rotation's axes
+    Box {
+        center: Vec3,
+        rotation: Quat,
+        half: Vec3,
+    },
+    Sphere {
+        center: Vec3,
+        radius: f32,
+    },
+    // outward normals and offsets, inside where every
+    // normal.dot(p) + offset <= 0, min/max bound the mesh it came from
+    Hull {
+        planes: Vec<(Vec3, f32)>,
+        min: Vec3,
+        max: Vec3,
+    },
+}
+
+impl Shape {
+    // the planes of a convex mesh's faces, winding doesn't matter,
+    // None if the mesh doesn't enclose anything
+    pub fn hull_from_mesh(tris: &[[Vec3; 3]]) -> Option<Self> {
+        let verts = tris.iter().flatten();
+        let count = tris.len() * 3;
+        if count == 0 {
+            return None;
+        }
+        let centroid = verts.clone().copied().sum::<Vec3>() / count as f32;
+        let min = verts.clone().copied().fold(Vec3::INFINITY, Vec3::min);
+        let max = verts.copied().fold(Vec3::NEG_INFINITY, Vec3::max);
+
+        let mut planes: Vec<(Vec3, f32)> = vec![];
+        for [a, b, c] in tris {
+            let n = (*b - *a).cross(*c - *a).normalize_or_zero();
+            if n == Vec3::ZERO {
+                continue;
+            }
+            // face away from the middle
+            let n = match n.dot(centroid - *a) > 0. {
+                true => -n,
+                false => n,
+            };
+            let offset = -n.dot(*a);
+            let dupe = planes
+                .iter()
+                .any(|(pn, po)| pn.dot(n) > 1. - FLOOR_EPS && (po - offset).abs() < FLOOR_EPS);
+            if !dupe {
+                planes.push((n, offset));
+            }
+        }
+
+        // a tetrahedron is the least that encloses a volume
+        if planes.len() < 4 {
+            return None;
+        }
+        Some(Shape::Hull { planes, min, max })
+    }
+
+    // world space bounds
+    pub fn bounds(&self) -> (Vec3, Vec3) {
+        match self {
+            Shape::Box {
+                center,
+                rotation,
+                half,
+            } => {
+                let m = glam::Mat3A::from_quat(*rotation);
+                let extent =
+                    m.x_axis.abs() * half.x + m.y_axis.abs() * half.y + m.z_axis.abs() * half.z;
+                (*center - extent, *center + extent)
+            }
+            Shape::Sphere { center, radius } => (
+                *center - Vec3::splat(*radius),
+                *center + Vec3::splat(*radius),
+            ),
+            Shape::Hull { min, max, .. } => (*min, *max),
+        }
+    }
+
+    // negative inside, exact for boxes and spheres, for hulls it's
+    // the furthest plane so corners count as slightly closer than they are
+    pub fn signed_distance(&self, p: Vec3) -> f32 {
+        match self {
+            Shape::Box {
+                center,
+                rotation,
+                half,
+            } => {
+                let local = rotation.inverse() * (p - *center);
+                let q = local.abs() - *half;
+                q.max(Vec3::ZERO).length() + q.max_element().min(0.)
+            }
+            Shape::Sphere { center, radius } => p.distance(*center) - radius,
+            Shape::Hull { planes, .. } => planes
+                .iter()
+                .map(|(n, offset)| n.dot(p) + offset)
+                .fold(f32::NEG_INFINITY, f32::max),
+        }
+    }
+
+    pub fn contains_point(&self, p: Vec3) -> bool {
+        self.signed_distance(p) <= 0.
+    }
+
+    // the capsule around segment a-b touches or is inside the shape
+    pub fn overlaps_capsule(&self, a: Vec3, b: Vec3, radius: f32) -> bool {
+        let p = closest_on_segment(a, b, |p| self.signed_distance(p));
+        self.signed_distance(p) <= radius
+    }
+}
+
+// the point of segment a-b where a convex distance function is lowest,
+// distance to a convex shape is convex along the segment, so narrowing
+// in on the lower side finds it
+fn closest_on_segment(a: Vec3, b: Vec3, distance: impl Fn(Vec3) -> f32) -> Vec3 {
+    let (mut lo, mut hi) = (0f32, 1f32);
+    for _ in 0..SEGMENT_SEARCH_STEPS {
+        let t1 = lo + (hi - lo) / 3.;
+        let t2 = hi - (hi - lo) / 3.;
+        if distance(a.lerp(b, t1)) < distance(a.lerp(b, t2)) {
+            hi = t2;
+        } else {
+            lo = t1;
+        }
+    }
+    a.lerp(b, (lo + hi) / 2.)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VolumeId(u32);
+
+// a volume touching more cells than this goes in VolumeSet::large instead
+const MAX_VOLUME_CELLS: i64 = 512;
+
+// shapes bucketed into GRID_SIZE cells by their bounds
+#[derive(Debug, Default)]
+pub struct VolumeSet {
+    // None once removed, ids aren't reused
+    shapes: Vec<Option<Shape>>,
+    grid: FxHashMap<Cell, Vec<VolumeId>>,
+    // too big to bucket, every query checks these
+    large: Vec<VolumeId>,
+}
+
+impl VolumeSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, shape: Shape) -> VolumeId {
+        let id = VolumeId(self.shapes.len() as u32);
+        let (min, max) = shape.bounds();
+        if cell_count(min, max) > MAX_VOLUME_CELLS {
+            self.large.push(id);
+        } else {
+            for cell in cells_between(min, max) {
+                self.grid.entry(cell).or_default().push(id);
+            }
+        }
+        self.shapes.push(Some(shape));
+        id
+    }
+
+    pub fn remove(&mut self, id: VolumeId) {
+        let Some(shape) = self.shapes.get_mut(id.0 as usize).and_then(Option::take) else {
+            return;
+        };
+        let (min, max) = shape.bounds();
+        if cell_count(min, max) > MAX_VOLUME_CELLS {
+            self.large.retain(|i| *i != id);
+            return;
+        }
+        for cell in cells_between(min, max) {
+            if let Some(ids) = self.grid.get_mut(&cell) {
+                ids.retain(|i| *i != id);
+            }
+        }
+    }
+
+    pub fn shape(&self, id: VolumeId) -> Option<&Shape> {
+        self.shapes.get(id.0 as usize)?.as_ref()
+    }
+
+    pub fn containing_point(&self, p: Vec3) -> Vec<VolumeId> {
+        let mut out: Vec<VolumeId> = self
+            .grid
+            .get(&cell_of(p))
+            .into_iter()
+            .flatten()
+            .chain(&self.large)
+            .copied()
+            .filter(|id| self.shape(*id).is_some_and(|s| s.contains_point(p)))
+            .collect();
+        out.sort();
+        out
+    }
+
+    // sorted, each id once
+    pub fn overlapping_capsule(&self, a: Vec3, b: Vec3, radius: f32) -> Vec<VolumeId> {
+        let min = a.min(b) - Vec3::splat(radius);
+        let max = a.max(b) + Vec3::splat(radius);
+
+        let mut candidates = vec![];
+        for cell in cells_between(min, max) {
+            candidates.extend(self.grid.get(&cell).into_iter().flatten().copied());
+        }
+        candidates.extend(&self.large);
+        candidates.sort();
+        candidates.dedup();
+
+        candidates.retain(|id| {
+            self.shape(*id).is_some_and(|s| {
+                let (s_min, s_max) = s.bounds();
+                s_min.cmple(max).all() && s_max.cmpge(min).all() && s.overlaps_capsule(a, b, radius)
+            })
+        });
+        candidates
+    }
+}
+
+fn cell_count(min: Vec3, max: Vec3) -> i64 {
+    let (x0, y0, z0) = cell_of(min);
+    let (x1, y1, z1) = cell_of(max);
+    let span = |a: i32, b: i32| (b as i64 - a as i64 + 1).max(0);
+    span(x0, x1)
+        .saturating_mul(span(y0, y1))
+        .saturating_mul(span(z0, z1))
+}
+
+fn cells_between(min: Vec3, max: Vec3) -> impl Iterator<Item = Cell> {
+    let (x0, y0, z0) = cell_of(min);
+    let (x1, y1, z1) = cell_of(max);
+    (x0..=x1).flat_map(move |x| (y0..=y1).flat_map(move |y| (z0..=z1).map(move |z| (x, y, z))))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overlap {
+    Enter,
+    Stay,
+    Exit,
+}
+
+// remembers what a moving capsule was in, to tell entering from staying
+#[derive(Debug, Default, Clone)]
+pub struct OverlapTracker {
+    inside: Vec<VolumeId>,
+}
+
+impl OverlapTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // call once per tick, exits come first, then the rest in id order
+    pub fn update(
+        &mut self,
+        set: &VolumeSet,
+        a: Vec3,
+        b: Vec3,
+        radius: f32,
+    ) -> Vec<(VolumeId, Overlap)> {
+        let now = set.overlapping_capsule(a, b, radius);
+
+        let mut out: Vec<(VolumeId, Overlap)> = self
+            .inside
+            .iter()
+            .filter(|id| now.binary_search(id).is_err())
+            .map(|id| (*id, Overlap::Exit))
+            .collect();
+        for id in &now {
+            let overlap = match self.inside.binary_search(id) {
+                Ok(_) => Overlap::Stay,
+                Err(_) => Overlap::Enter,
+            };
+            out.push((*id, overlap));
+        }
+
+        self.inside = now;
+        out
+    }
+
+    pub fn inside(&self) -> &[VolumeId] {
+        &self.inside
+    }
+
+    // forget everything without reporting exits, e.g. after a teleport
+    pub fn clear(&mut self) {
+        self.inside.clear();
+    }
+}
+
+// a moving thing's shape, gathered fresh each tick
+#[derive(Debug, Clone, Copy)]
+pub enum ColliderShape {
+    // segment a-b grown by radius
+    Capsule { a: Vec3, b: Vec3, radius: f32 },
+    Sphere { center: Vec3, radius: f32 },
+    Aabb { min: Vec3, max: Vec3 },
+}
+
+impl ColliderShape {
+    pub fn bounds(&self) -> (Vec3, Vec3) {
+        match *self {
+            ColliderShape::Capsule { a, b, radius } => (
+                a.min(b) - Vec3::splat(radius),
+                a.max(b) + Vec3::splat(radius),
+            ),
+            ColliderShape::Sphere { center, radius } => {
+                (center - Vec3::splat(radius), center + Vec3::splat(radius))
+            }
+            ColliderShape::Aabb { min, max } => (min, max),
+        }
+    }
+
+    // a sphere is a capsule that doesn't go anywhere
+    fn as_capsule(&self) -> Option<(Vec3, Vec3, f32)> {
+        match *self {
+            ColliderShape::Capsule { a, b, radius } => Some((a, b, radius)),
+            ColliderShape::Sphere { center, radius } => Some((center, center, radius)),
+            ColliderShape::Aabb { .. } => None,
+        }
+    }
+
+    // the way to move self, and how far, to stop overlapping other
+    pub fn contact(&self, other: &ColliderShape) -> Option<(Vec3, f32)> {
+        match (self.as_capsule(), other.as_capsule(), self, other) {
+            (Some((a1, b1, r1)), Some((a2, b2, r2)), _, _) => {
+                let (p1, p2) = closest_points_segments(a1, b1, a2, b2);
+                let depth = r1 + r2 - p1.distance(p2);
+                // stacked exactly, push apart sideways
+                let fallback = ((a1 + b1) - (a2 + b2)) * Vec3::new(1., 0., 1.);
+                let normal = (p1 - p2).normalize_or(fallback.normalize_or(Vec3::X));
+                (depth > 0.).then_some((normal, depth))
+            }
+            (Some((a, b, r)), None, _, ColliderShape::Aabb { min, max }) => {
+                capsule_aabb_contact(a, b, r, *min, *max)
+            }
+            (None, Some((a, b, r)), ColliderShape::Aabb { min, max }, _) => {
+                capsule_aabb_contact(a, b, r, *min, *max).map(|(n, d)| (-n, d))
+            }
+            (
+                None,
+                None,
+                ColliderShape::Aabb { min: a0, max: a1 },
+                ColliderShape::Aabb { min: b0, max: b1 },
+            ) => {
+                let overlap = a1.min(*b1) - a0.max(*b0);
+                if overlap.min_element() <= 0. {
+                    return None;
+                }
+                // out along the shallowest axis
+                let axis = overlap.min_position();
+                let mut normal = Vec3::ZERO;
+                normal[axis] = match (*a0 + *a1)[axis] < (*b0 + *b1)[axis] {
+                    true => -1.,
+                    false => 1.,
+                };
+                Some((normal, overlap[axis]))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn capsule_aabb_contact(
+    a: Vec3,
+    b: Vec3,
+    radius: f32,
+    min: Vec3,
+    max: Vec3,
+) -> Option<(Vec3, f32)> {
+    let p = closest_on_segment(a, b, |p| p.distance(p.clamp(min, max)));
+    let q = p.clamp(min, max);
+
+    if p != q {
+        let dist = p.distance(q);
+        return (dist < radius).then(|| ((p - q) / dist, radius - dist));
+    }
+
+    // the segment goes through the box, out the nearest face
+    let to_min = p - min;
+    let to_max = max - p;
+    let (axis, depth, sign) = (0..3)
+        .flat_map(|i| [(i, to_min[i], -1.), (i, to_max[i], 1.)])
+        .min_by(|x, y| x.1.total_cmp(&y.1))?;
+    let mut normal = Vec3::ZERO;
+    normal[axis] = sign;
+    Some((normal, depth + radius))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Collider {
+    pub shape: ColliderShape,
+    // share of a push is the other's mass over both,
+    // INFINITY pushes without being pushed
+    pub mass: f32,
+    // overlaps are reported but nothing is pushed
+    pub sensor: bool,
+}
+
+impl Collider {
+    pub fn solid(shape: ColliderShape, mass: f32) -> Self {
+        Self {
+            shape,
+            mass,
+            sensor: false,
+        }
+    }
+
+    pub fn sensor(shape: ColliderShape) -> Self {
+        Self {
+            shape,
+            mass: f32::INFINITY,
+            sensor: true,
+        }
+    }
+}
+
+// two colliders overlapping, normal moves a out of b
+#[derive(Debug, Clone, Copy)]
+pub struct Contact<K> {
+    pub a: K,
+    pub b: K,
+    pub normal: Vec3,
+    pub depth: f32,
+    // either was a sensor
+    pub sensor: bool,
+}
+
+// colliders keyed by whatever owns them, e.g. an entity handle,
+// cleared and refilled each tick
+#[derive(Debug)]
+pub struct ColliderSet<K> {
+    colliders: Vec<(K, Collider)>,
+}
+
+impl<K> Default for ColliderSet<K> {
+    fn default() -> Self {
+        Self { colliders: vec![] }
+    }
+}
+
+impl<K: Copy> ColliderSet<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.colliders.clear();
+    }
+
+    pub fn add(&mut self, key: K, collider: Collider) {
+        self.colliders.push((key, collider));
+    }
+
+    pub fn len(&self) -> usize {
+        self.colliders.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colliders.is_empty()
+    }
+
+    // indices of colliders whose bounds overlap, sweeping along x
+    pub fn pairs(&self) -> Vec<(usize, usize)> {
+        let bounds: Vec<(Vec3, Vec3)> = self
+            .colliders
+            .iter()
+            .map(|(_, c)| c.shape.bounds())
+            .collect();
+        let mut order: Vec<usize> = (0..bounds.len()).collect();
+        order.sort_by(|i, j| bounds[*i].0.x.total_cmp(&bounds[*j].0.x));
+
+        let mut pairs = vec![];
+        for (n, &i) in order.iter().enumerate() {
+            let (min, max) = bounds[i];
+            for &j in &order[n + 1..] {
+                let (o_min, o_max) = bounds[j];
+                // sorted, nothing later starts before this ends
+                if o_min.x > max.x {
+                    break;
+                }
+                if o_min.cmple(max).all() && o_max.cmpge(min).all() {
+                    pairs.push((i.min(j), i.max(j)));
+                }
+            }
+        }
+        pairs
+    }
+
+    pub fn contacts(&self) -> Vec<Contact<K>> {
+        self.pairs()
+            .into_iter()
+            .filter_map(|(i, j)| {
+                let (a, ca) = self.colliders[i];
+                let (b, cb) = self.colliders[j];
+                let (normal, depth) = ca.shape.contact(&cb.shape)?;
+                Some(Contact {
+                    a,
+                    b,
+                    normal,
+                    depth,
+                    sensor: ca.sensor || cb.sensor,
+                })
+            })
+            .collect()
+    }
+
+    // how far to move each solid collider to undo this tick's contacts,
+    // only ones that need moving, in the order they were added
+    pub fn separation(&self) -> Vec<(K, Vec3)> {
+        let mut moves = vec![Vec3::ZERO; self.colliders.len()];
+
+        for (i, j) in self.pairs() {
+            let (_, ca) = self.colliders[i];
+            let (_, cb) = self.colliders[j];
+            if ca.sensor || cb.sensor {
+                continue;
+            }
+            let Some((normal, depth)) = ca.shape.contact(&cb.shape) else {
+                continue;
+            };
+
+            let (wa, wb) = (1. / ca.mass, 1. / cb.mass);
+            if wa + wb <= 0. {
+                continue;
+            }
+            moves[i] += normal * depth * wa / (wa + wb);
+            moves[j] -= normal * depth * wb / (wa + wb);
+        }
+
+        self.colliders
+            .iter()
+            .zip(moves)
+            .filter(|(_, m)| *m != Vec3::ZERO)
+            .map(|((k, _), m)| (*k, m))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    // flat square floor at height y, centred on (x, z)
+    fn floor_square(x: f32, y: f32, z: f32, half: f32) -> Vec<Surface> {
+        let a = Vec3::new(x - half, y, z - half);
+        let b = Vec3::new(x + half, y, z - half);
+        let c = Vec3::new(x + half, y, z + half);
+        let d = Vec3::new(x - half, y, z + half);
+        let up = Vec3::new(0., 1., 0.);
+        vec![Surface::new([a, c, b], up), Surface::new([a, d, c], up)]
+    }
+
+    #[test]
+    fn cell_of_rounds_towards_negative_infinity() {
+        assert_eq!(cell_of(Vec3::new(0.1, 0.1, 0.1)), (0, 0, 0));
+        assert_eq!(cell_of(Vec3::new(-0.1, -0.1, -0.1)), (-1, -1, -1));
+        assert_eq!(cell_of(Vec3::new(-GRID_SIZE, 0., GRID_SIZE)), (-1, 0, 1));
+        assert_eq!(cell_of(Vec3::new(-GRID_SIZE - 0.01, 0., 0.)), (-2, 0, 0));
+    }
+
+    #[test]
+    fn surface_straddling_origin_is_in_cells_on_both_sides() {
+        let grid = SurfaceGrid::new(floor_square(0., 0., 0., 3.));
+
+        for pos in [
+            Vec3::new(-1., 0.5, -1.),
+            Vec3::new(1., 0.5, 1.),
+            Vec3::new(-1., -0.5, 1.),
+            Vec3::new(1., 0.5, -1.),
+        ] {
+            let surfaces = grid.surfaces_in_cell(cell_of(pos)).unwrap_or_default();
+            assert_eq!(surfaces.len(), 2, "at {pos:?}");
+        }
+
+        assert!(grid.surfaces_in_cell((-3, 0, -3)).is_none());
+        assert!(grid.surfaces_in_cell((3, 0, 3)).is_none());
+    }
+
+    #[test]
+    fn negative_surfaces_dont_alias_positive_cells() {
+        let grid = SurfaceGrid::new(floor_square(-12., -7., -12., 1.));
+        let cell = cell_of(Vec3::new(-12., -7., -12.));
+        assert_eq!(cell, (-3, -2, -3));
+
+        assert_eq!(grid.surfaces_in_cell(cell).unwrap_or_default().len(), 2);
+        assert!(grid.surfaces_in_cell((2, 1, 2)).is_none());
+        assert!(grid.surfaces_in_cell((3, 2, 3)).is_none());
+    }
+
+    #[test]
+    fn adjacent_lookup_crosses_origin_and_dedupes() {
+        let grid = SurfaceGrid::new(floor_square(0., 0., 0., 3.));
+
+        let surfaces = grid.surfaces_in_cell_and_adjacent((-1, -1, -1));
+        assert_eq!(surfaces.len(), 2);
+
+        let surfaces = grid.surfaces_in_cell_and_adjacent((-4, 0, 0));
+        assert!(surfaces.is_empty());
+    }
+
+    #[test]
+    fn grid_rebuilt_from_cell_indices_matches() {
+        let mut surfaces = floor_square(-12., -7., -12., 1.);
+        surfaces.extend(floor_square(0., 0., 0., 3.));
+        let grid = SurfaceGrid::new(surfaces);
+
+        let (surfaces, cells) = grid.cell_indices();
+        assert!(cells.is_sorted_by_key(|(cell, _)| *cell));
+        let baked =
+            SurfaceGrid::from_cell_indices(surfaces.clone(), cells.clone(), GRID_SIZE).unwrap();
+
+        for (cell, indices) in &cells {
+            let got = baked.surfaces_in_cell(*cell).unwrap();
+            let want = grid.surfaces_in_cell(*cell).unwrap();
+            assert_eq!(got.len(), indices.len());
+            for (g, w) in got.iter().zip(want) {
+                assert_eq!(g.triangle().verts(), w.triangle().verts());
+            }
+        }
+        assert_eq!(baked.all_surfaces().unwrap().len(), 4);
+
+        let mut bad = cells;
+        bad[0].1.push(surfaces.len() as u32);
+        assert!(SurfaceGrid::from_cell_indices(surfaces, bad, GRID_SIZE).is_none());
+    }
+
+    #[test]
+    fn translating_body_displaces_points() {
+        let mut grid = SurfaceGrid::new(vec![]);
+        let id = grid.add_body(floor_square(0., 0., 0., 2.));
+        grid.set_body_transform(id, Affine3A::from_translation(glam::Vec3::new(1., 0., 0.)));
+        grid.set_body_transform(id, Affine3A::from_translation(glam::Vec3::new(3., 0., 2.)));
+
+        for point in [Vec3::ZERO, Vec3::new(5., 1., -3.)] {
+            let d = grid.body_displacement(id, point);
+            assert!(d.distance(Vec3::new(2., 0., 2.)) < 1e-5, "{d} at {point}");
+        }
+    }
+
+    #[test]
+    fn rotating_body_displaces_about_its_origin() {
+        let mut grid = SurfaceGrid::new(vec![]);
+        let id = grid.add_body(floor_square(0., 0., 0., 2.));
+        let at = glam::Vec3::new(10., 0., 10.);
+        grid.set_body_transform(id, Affine3A::from_translation(at));
+        grid.set_body_transform(
+            id,
+            Affine3A::from_rotation_translation(
+                Quat::from_rotation_y(core::f32::consts::FRAC_PI_2),
+                at,
+            ),
+        );
+
+        // a quarter turn takes +x round to -z, the origin stays put
+        let d = grid.body_displacement(id, Vec3::new(11., 0., 10.));
+        assert!(d.distance(Vec3::new(-1., 0., -1.)) < 1e-5, "{d}");
+        let d = grid.body_displacement(id, Vec3::new(10., 0., 10.));
+        assert!(d.length() < 1e-5, "{d}");
+
+        // unknown bodies don't move anything
+        let other = SurfaceGrid::new(vec![]);
+        assert_eq!(other.body_displacement(id, Vec3::ZERO), Vec3::ZERO);
+    }
+
+    #[test]
+    fn body_surfaces_near_follow_transform() {
+        let mut grid = SurfaceGrid::new(floor_square(0., 0., 0., 2.));
+        let id = grid.add_body(floor_square(0., 0., 0., 2.));
+        let carried = |grid: &SurfaceGrid, pos| {
+            let near = grid.surfaces_near(pos);
+            near.iter().filter(|s| s.body() == Some(id)).count()
+        };
+        assert_eq!(carried(&grid, Vec3::new(0., 0.1, 0.)), 2);
+
+        let to = glam::Vec3::new(100., 5., 0.);
+        grid.set_body_transform(id, Affine3A::from_translation(to));
+        assert_eq!(carried(&grid, Vec3::new(0., 0.1, 0.)), 0);
+        assert_eq!(carried(&grid, Vec3::new(100., 5.1, 0.)), 2);
+        assert_eq!(grid.surfaces_near(Vec3::new(100., 5.1, 0.)).len(), 2);
+
+        // moved, and still floors
+        for s in grid.body_surfaces_near(Vec3::new(100., 5.1, 0.), 1.) {
+            assert!(matches!(s, Surface::Floor(_)));
+            assert!(s.triangle().verts().iter().all(|v| v.y == 5.));
+        }
+    }
+
+    #[test]
+    fn floor_found_below_origin() {
+        let grid = SurfaceGrid::new(floor_square(0., -1., 0., 3.));
+
+        for pos in [Vec3::new(-2., -0.9, -2.), Vec3::new(2., -0.9, -2.)] {
+            let surfaces = grid.surfaces_in_cell(cell_of(pos)).unwrap_or_default();
+            let hit = find_floor_height_hotdog_v4(pos, 1., 0.5, surfaces, 0.5);
+            match hit {
+                Some((Surface::Floor(_), y)) => assert!((y + 1.).abs() < 1e-4, "at {pos:?}"),
+                other => panic!("no floor at {pos:?}: {other:?}"),
+            }
+        }
+    }
+
+    // vertical square wall in the x = x plane facing -x, spanning y and z
+    fn wall_facing_neg_x(x: f32, y: (f32, f32), z: (f32, f32)) -> Vec<Surface> {
+        let a = Vec3::new(x, y.0, z.0);
+        let b = Vec3::new(x, y.1, z.0);
+        let c = Vec3::new(x, y.1, z.1);
+        let d = Vec3::new(x, y.0, z.1);
+        let n = Vec3::new(-1., 0., 0.);
+        vec![Surface::new([a, b, c], n), Surface::new([a, c, d], n)]
+    }
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-3
+    }
+
+    #[test]
+    fn raycast_hits_floor_below() {
+        let grid = SurfaceGrid::new(floor_square(0., 0., 0., 3.));
+        let hit = grid
+            .raycast(Vec3::new(0.5, 10., 0.5), Vec3::new(0., -1., 0.), 100.)
+            .unwrap();
+        assert!(close(hit.distance, 10.), "{}", hit.distance);
+        assert!(close(hit.normal.y, 1.));
+    }
+
+    #[test]
+    fn raycast_ignores_back_faces_and_range() {
+        let grid = SurfaceGrid::new(floor_square(0., 0., 0., 3.));
+        let down = Vec3::new(0., -1., 0.);
+        assert!(
+            grid.raycast(Vec3::new(0.5, -10., 0.5), -down, 100.)
+                .is_none()
+        );
+        assert!(grid.raycast(Vec3::new(0.5, 10., 0.5), down, 9.).is_none());
+        assert!(grid.raycast(Vec3::new(5., 10., 0.5), down, 100.).is_none());
+    }
+
+    #[test]
+    fn casts_that_miss_with_infinite_range_end() {
+        let grid = SurfaceGrid::new(floor_square(0., 0., 0., 3.));
+        let up = Vec3::new(0., 1., 0.);
+        let side = Vec3::new(1., 0.2, 0.);
+        for dist in [f32::INFINITY, f32::NAN] {
+            assert!(grid.raycast(Vec3::new(0.5, 10., 0.5), up, dist).is_none());
+            assert!(grid.raycast(Vec3::new(0.5, 10., 0.5), side, dist).is_none());
+            assert!(
+                grid.sphere_cast(Vec3::new(0.5, 10., 0.5), 0.5, side, dist)
+                    .is_none()
+            );
+        }
+        let hit = grid
+            .raycast(Vec3::new(0.5, 10., 0.5), -up, f32::INFINITY)
+            .unwrap();
+        assert!(close(hit.distance, 10.), "{}", hit.distance);
+    }
+
+    #[test]
+    fn raycast_walks_cells_across_origin() {
+        // a wall far off in -x, facing +x
+        let n = Vec3::new(1., 0., 0.);
+        let a = Vec3::new(-40., -5., -5.);
+        let b = Vec3::new(-40., 5., -5.);
+        let c = Vec3::new(-40., 5., 5.);
+        let grid = SurfaceGrid::new(vec![Surface::new([a, b, c], n)]);
+
+        let hit = grid
+            .raycast(Vec3::new(12., 1., -3.), Vec3::new(-1., 0., 0.), 100.)
+            .unwrap();
+        assert!(close(hit.distance, 52.), "{}", hit.distance);
+    }
+
+    #[test]
+    fn raycast_returns_nearest() {
+        let mut surfaces = wall_facing_neg_x(30., (-5., 5.), (-5., 5.));
+        surfaces.extend(wall_facing_neg_x(20., (-5., 5.), (-5., 5.)));
+        let grid = SurfaceGrid::new(surfaces);
+
+        let hit = grid
+            .raycast(Vec3::new(0., 0., 0.), Vec3::new(1., 0., 0.), 100.)
+            .unwrap();
+        assert!(close(hit.distance, 20.), "{}", hit.distance);
+    }
+
+    // small triangles scattered through a 60 unit cube
+    fn scattered(n: usize, seed: u64) -> Vec<Surface> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut point = |range: f32| {
+            Vec3::new(
+                rng.random_range(-range..range),
+                rng.random_range(-range..range),
+                rng.random_range(-range..range),
+            )
+        };
+        (0..n)
+            .map(|_| {
+                let c = point(30.);
+                let [a, b, d] = [c + point(2.), c + point(2.), c + point(2.)];
+                Surface::new([a, b, d], get_face_normal(a, b, d))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn grids_and_bvh_agree_on_casts() {
+        let surfaces = scattered(1000, 49);
+        let grid = SurfaceGrid::new(surfaces.clone());
+        let coarse = SurfaceGrid::with_cell_size(surfaces.clone(), 12.);
+        let bvh = SurfaceBvh::new(surfaces);
+        let queries: [&dyn SurfaceQuery; 3] = [&grid, &coarse, &bvh];
+
+        let mut rng = StdRng::seed_from_u64(50);
+        let mut hits = 0;
+        for _ in 0..200 {
+            let origin = Vec3::new(
+                rng.random_range(-30.0..30.),
+                rng.random_range(-30.0..30.),
+                rng.random_range(-30.0..30.),
+            );
+            let dir = Vec3::new(
+                rng.random_range(-1.0..1.),
+                rng.random_range(-1.0..1.),
+                rng.random_range(-1.0..1.),
+            );
+
+            let rays = queries.map(|q| q.raycast(origin, dir, 80.).map(|h| h.distance));
+            assert_eq!(rays[0], rays[1], "ray from {origin} along {dir}");
+            assert_eq!(rays[0], rays[2], "ray from {origin} along {dir}");
+
+            let b = origin + Vec3::new(0., 1.5, 0.);
+            let sweeps =
+                queries.map(|q| q.capsule_cast(origin, b, 1., dir, 20.).map(|h| h.distance));
+            assert_eq!(sweeps[0], sweeps[1], "capsule from {origin} along {dir}");
+            assert_eq!(sweeps[0], sweeps[2], "capsule from {origin} along {dir}");
+
+            hits += rays[0].is_some() as usize + sweeps[0].is_some() as usize;
+        }
+        assert!(hits > 50, "only {hits} hits");
+    }
+
+    #[test]
+    fn bvh_overlap_matches_brute_force() {
+        let bvh = SurfaceBvh::new(scattered(400, 51));
+
+        let mut rng = StdRng::seed_from_u64(52);
+        for _ in 0..100 {
+            let min = Vec3::new(
+                rng.random_range(-35.0..35.),
+                rng.random_range(-35.0..35.),
+                rng.random_range(-35.0..35.),
+            );
+            let max = min + Vec3::splat(rng.random_range(0.0..15.));
+
+            let expected = bvh
+                .surfaces()
+                .iter()
+                .filter(|s| {
+                    let (lo, hi) = surface_bounds(s);
+                    boxes_overlap(min, max, lo, hi)
+                })
+                .count();
+            assert_eq!(bvh.surfaces_overlapping(min, max).len(), expected);
+        }
+    }
+
+    #[test]
+    fn cells_are_kept_above_minimum_size() {
+        let grid = SurfaceGrid::with_cell_size(floor_square(0., 0., 0., 3.), 1.);
+        assert_eq!(grid.cell_size(), MIN_GRID_SIZE);
+
+        let (surfaces, cells) = grid.cell_indices();
+        assert!(SurfaceGrid::from_cell_indices(surfaces.clone(), cells.clone(), 1.).is_none());
+        let baked = SurfaceGrid::from_cell_indices(surfaces, cells, MIN_GRID_SIZE).unwrap();
+        assert_eq!(baked.cell_of(Vec3::new(-0.1, 4.1, 8.)), (-1, 1, 2));
+    }
+
+    #[test]
+    fn sphere_cast_lands_on_face_and_edge() {
+        let grid = SurfaceGrid::new(floor_square(0., 0., 0., 3.));
+        let down = Vec3::new(0., -1., 0.);
+
+        let hit = grid
+            .sphere_cast(Vec3::new(0.5, 5., 0.5), 1., down, 100.)
+            .unwrap();
+        assert!(close(hit.distance, 4.), "{}", hit.distance);
+        assert!(close(hit.normal.y, 1.));
+
+        // half a radius past the edge at x = 3
+        let hit = grid
+            .sphere_cast(Vec3::new(3.5, 5., 0.), 1., down, 100.)
+            .unwrap();
+        assert!(close(hit.distance, 5. - 0.75f32.sqrt()), "{}", hit.distance);
+        assert!(hit.normal.x > 0.);
+
+        assert!(
+            grid.sphere_cast(Vec3::new(4.5, 5., 0.), 1., down, 100.)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn capsule_cast_hits_wall() {
+        let grid = SurfaceGrid::new(wall_facing_neg_x(10., (0., 10.), (-5., 5.)));
+        let a = Vec3::new(0., 1., 0.);
+        let b = Vec3::new(0., 3., 0.);
+
+        let hit = grid
+            .capsule_cast(a, b, 1., Vec3::new(1., 0., 0.), 100.)
+            .unwrap();
+        assert!(close(hit.distance, 9.), "{}", hit.distance);
+        assert!(close(hit.normal.x, -1.));
+
+        assert!(
+            grid.capsule_cast(a, b, 1., Vec3::new(-1., 0., 0.), 100.)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn capsule_cast_hits_wall_between_its_ends() {
+        // a strip only reachable by the middle of the capsule
+        let grid = SurfaceGrid::new(wall_facing_neg_x(10., (1.8, 2.2), (-5., 5.)));
+        let hit = grid
+            .capsule_cast(
+                Vec3::new(0., 0., 0.),
+                Vec3::new(0., 4., 0.),
+                1.,
+                Vec3::new(1., 0., 0.),
+                100.,
+            )
+            .unwrap();
+        assert!(close(hit.distance, 9.), "{}", hit.distance);
+    }
+
+    #[test]
+    fn capsule_cast_taller_than_a_cell() {
+        // only the top of a 3 cell tall capsule reaches it
+        let grid = SurfaceGrid::new(wall_facing_neg_x(10., (13., 14.), (-5., 5.)));
+        let hit = grid
+            .capsule_cast(
+                Vec3::new(0., 0., 0.),
+                Vec3::new(0., 13.5, 0.),
+                1.,
+                Vec3::new(1., 0., 0.),
+                100.,
+            )
+            .unwrap();
+        assert!(close(hit.distance, 9.), "{}", hit.distance);
+    }
+
+    #[test]
+    fn capsule_cast_already_touching() {
+        let grid = SurfaceGrid::new(wall_facing_neg_x(10., (0., 10.), (-5., 5.)));
+        let hit = grid
+            .capsule_cast(
+                Vec3::new(9.5, 1., 0.),
+                Vec3::new(9.5, 3., 0.),
+                1.,
+                Vec3::new(1., 0., 0.),
+                100.,
+            )
+            .unwrap();
+        assert_eq!(hit.distance, 0.);
+        assert!(close(hit.normal.x, -1.));
+    }
+
+    #[test]
+    fn closest_segments() {
+        let (a, b) = closest_points_segments(
+            Vec3::new(-1., 0., 0.),
+            Vec3::new(1., 0., 0.),
+            Vec3::new(0., -1., 2.),
+            Vec3::new(0., 1., 2.),
+        );
+        assert!(a.distance(Vec3::ZERO) < 1e-5);
+        assert!(b.distance(Vec3::new(0., 0., 2.)) < 1e-5);
+    }
+
+    #[test]
+    fn controller_falls_and_lands_on_floor() {
+        let grid = SurfaceGrid::new(floor_square(0., 0., 0., 6.));
+        let mut cc = CharacterController::new(Vec3::new(0.5, 3., 0.5), 2.7, 1.);
+        cc.on_ground = false;
+
+        let mut landed = None;
+        for _ in 0..120 {
+            let report = cc.step(1. / 60., Vec3::ZERO, false, &grid);
+            landed = landed.or(report.landed_speed);
+        }
+
+        assert!(cc.on_ground);
+        assert!(close(cc.position.y, 0.), "{}", cc.position.y);
+        assert!(landed.unwrap() > 0.);
+    }
+
+    #[test]
+    fn controller_walks_into_wall_and_stops() {
+        let mut surfaces = floor_square(0., 0., 0., 6.);
+        surfaces.extend(wall_facing_neg_x(3., (-1., 4.), (-6., 6.)));
+        let grid = SurfaceGrid::new(surfaces);
+        let mut cc = CharacterController::new(Vec3::new(0.5, 0., 0.5), 2.7, 1.);
+
+        let mut hit_wall = false;
+        for _ in 0..120 {
+            let report = cc.step(1. / 60., Vec3::new(1., 0., 0.), false, &grid);
+            hit_wall |= report.hit_wall;
+        }
+
+        assert!(hit_wall);
+        assert!(cc.on_ground);
+        // kept a radius (less the skin) away from the wall
+        assert!(
+            (cc.position.x - (3. - cc.radius)).abs() < 0.05,
+            "{}",
+            cc.position.x
+        );
+    }
+
+    #[test]
+    fn controller_doesnt_tunnel_through_thin_floor() {
+        let grid = SurfaceGrid::new(floor_square(0., 0., 0., 6.));
+        let mut cc = CharacterController::new(Vec3::new(0.5, 20., 0.5), 2.7, 1.);
+        cc.on_ground = false;
+        cc.velocity = Vec3::new(0., cc.terminal_velocity, 0.);
+
+        // 21.6 units in a single step, well past the floor
+        let report = cc.step(0.1, Vec3::ZERO, false, &grid);
+
+        assert!(cc.on_ground);
+        assert!(close(cc.position.y, 0.), "{}", cc.position.y);
+        assert!(report.landed_speed.is_some());
+    }
+
+    fn with_material(surfaces: Vec<Surface>, material: Material) -> Vec<Surface> {
+        surfaces
+            .iter()
+            .map(|s| {
+                let t = s.triangle();
+                Surface::new_with_material(t.verts, t.normal, material)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn material_slide_angle_reclassifies() {
+        // 45 degrees
+        let n = Vec3::new(0., 1., 1.).normalize();
+        let verts = [Vec3::ZERO, Vec3::X, Vec3::new(0., 1., -1.)];
+        assert!(matches!(Surface::new(verts, n), Surface::Floor(_)));
+
+        let steep = Material {
+            slide_angle: Some(30f32.to_radians()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            Surface::new_with_material(verts, n, steep),
+            Surface::Slide(_)
+        ));
+    }
+
+    // horizontal speed after walking for a second
+    fn walk_speed(material: Material) -> f32 {
+        let grid = SurfaceGrid::new(with_material(floor_square(0., 0., 0., 12.), material));
+        let mut cc = CharacterController::new(Vec3::new(-10., 0., 0.5), 2.7, 1.);
+        cc.step(1. / 60., Vec3::ZERO, false, &grid);
+        for _ in 0..60 {
+            cc.step(1. / 60., Vec3::X, false, &grid);
+        }
+        cc.velocity.x
+    }
+
+    #[test]
+    fn ice_and_mud_change_ground_movement() {
+        let plain = walk_speed(Material::default());
+        let ice = walk_speed(Material {
+            ice: true,
+            ..Default::default()
+        });
+        let mud = walk_speed(Material {
+            mud: true,
+            ..Default::default()
+        });
+
+        // ice is still getting up to speed, mud tops out lower
+        assert!(ice < plain * 0.9, "{ice} vs {plain}");
+        assert!(mud < plain * 0.5, "{mud} vs {plain}");
+    }
+
+    #[test]
+    fn bouncy_floor_bounces_instead_of_landing() {
+        let bouncy = Material {
+            bouncy: true,
+            ..Default::default()
+        };
+        let grid = SurfaceGrid::new(with_material(floor_square(0., 0., 0., 6.), bouncy));
+        let mut cc = CharacterController::new(Vec3::new(0.5, 5., 0.5), 2.7, 1.);
+        cc.on_ground = false;
+        cc.velocity.y = -20.;
+
+        let mut landed = false;
+        let mut rose = false;
+        for _ in 0..30 {
+            let report = cc.step(1. / 60., Vec3::ZERO, false, &grid);
+            landed |= report.landed_speed.is_some();
+            rose |= cc.velocity.y > 0.;
+        }
+
+        assert!(rose);
+        assert!(!landed);
+        assert!(!cc.on_ground);
+    }
+
+    fn unit_cube_mesh(center: Vec3) -> Vec<[Vec3; 3]> {
+        let c = |x: f32, y: f32, z: f32| center + Vec3::new(x, y, z) * 0.5;
+        #[rustfmt::skip]
+        let quads = [
+            [c(-1., -1., -1.), c(1., -1., -1.), c(1., -1., 1.), c(-1., -1., 1.)],
+            [c(-1., 1., -1.), c(1., 1., -1.), c(1., 1., 1.), c(-1., 1., 1.)],
+            [c(-1., -1., -1.), c(-1., 1., -1.), c(-1., 1., 1.), c(-1., -1., 1.)],
+            [c(1., -1., -1.), c(1., 1., -1.), c(1., 1., 1.), c(1., -1., 1.)],
+            [c(-1., -1., -1.), c(1., -1., -1.), c(1., 1., -1.), c(-1., 1., -1.)],
+            [c(-1., -1., 1.), c(1., -1., 1.), c(1., 1., 1.), c(-1., 1., 1.)],
+        ];
+        // mixed winding on purpose
+        quads
+            .iter()
+            .flat_map(|[a, b, c, d]| [[*a, *b, *c], [*a, *d, *c]])
+            .collect()
+    }
+
+    #[test]
+    fn shapes_contain_points() {
+        let rotated = Shape::Box {
+            center: Vec3::new(10., 0., 0.),
+            rotation: Quat::from_rotation_y(core::f32::consts::FRAC_PI_4),
+            half: Vec3::new(2., 1., 0.5),
+        };
+        // along the rotated long axis
+        let along = Vec3::new(1., 0., -1.).normalize() * 1.9;
+        assert!(rotated.contains_point(Vec3::new(10., 0., 0.) + along));
+        assert!(!rotated.contains_point(Vec3::new(11.9, 0., 0.)));
+
+        let sphere = Shape::Sphere {
+            center: Vec3::new(-3., -3., -3.),
+            radius: 1.,
+        };
+        assert!(sphere.contains_point(Vec3::new(-3.5, -3., -3.)));
+        assert!(!sphere.contains_point(Vec3::new(-4.5, -3., -3.)));
+
+        let hull = Shape::hull_from_mesh(&unit_cube_mesh(Vec3::new(0., 5., 0.))).unwrap();
+        let Shape::Hull { planes, .. } = &hull else {
+            panic!("not a hull");
+        };
+        assert_eq!(planes.len(), 6);
+        assert!(hull.contains_point(Vec3::new(0.4, 5.4, -0.4)));
+        assert!(!hull.contains_point(Vec3::new(0.6, 5., 0.)));
+    }
+
+    #[test]
+    fn hull_needs_a_volume() {
+        let flat: Vec<[Vec3; 3]> = floor_square(0., 0., 0., 1.)
+            .iter()
+            .map(|s| s.triangle().verts)
+            .collect();
+        assert!(Shape::hull_from_mesh(&flat).is_none());
+    }
+
+    #[test]
+    fn capsule_overlap_uses_closest_point_on_segment() {
+        let sphere = Shape::Sphere {
+            center: Vec3::ZERO,
+            radius: 1.,
+        };
+        // the middle of the segment passes 1.4 from the center
+        let a = Vec3::new(-5., 1.4, 0.);
+        let b = Vec3::new(5., 1.4, 0.);
+        assert!(sphere.overlaps_capsule(a, b, 0.5));
+        assert!(!sphere.overlaps_capsule(a, b, 0.3));
+
+        let cube = Shape::Box {
+            center: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            half: Vec3::ONE,
+        };
+        // standing next to it, feet to head
+        let a = Vec3::new(1.8, -1., 0.);
+        let b = Vec3::new(1.8, 1.5, 0.);
+        assert!(cube.overlaps_capsule(a, b, 1.));
+        assert!(!cube.overlaps_capsule(a, b, 0.7));
+    }
+
+    #[test]
+    fn volume_set_finds_shapes_across_cells() {
+        let mut set = VolumeSet::new();
+        let water = set.add(Shape::Box {
+            center: Vec3::new(0., -2., 0.),
+            rotation: Quat::IDENTITY,
+            half: Vec3::new(20., 2., 20.),
+        });
+        let orb = set.add(Shape::Sphere {
+            center: Vec3::new(-12., -1., -12.),
+            radius: 2.,
+        });
+
+        assert_eq!(set.containing_point(Vec3::new(15., -1., -15.)), vec![water]);
+        assert_eq!(
+            set.containing_point(Vec3::new(-12., -1., -12.)),
+            vec![water, orb]
+        );
+        assert!(set.containing_point(Vec3::new(0., 1., 0.)).is_empty());
+
+        let near =
+            set.overlapping_capsule(Vec3::new(-10., 0.5, -12.), Vec3::new(-10., 2., -12.), 1.);
+        assert_eq!(near, vec![water, orb]);
+
+        set.remove(water);
+        assert!(set.shape(water).is_none());
+        assert_eq!(set.containing_point(Vec3::new(-12., -1., -12.)), vec![orb]);
+    }
+
+    #[test]
+    fn volume_set_keeps_large_volumes_out_of_the_grid() {
+        let mut set = VolumeSet::new();
+        let sea = set.add(Shape::Box {
+            center: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            half: Vec3::new(10000., 10., 10000.),
+        });
+        let orb = set.add(Shape::Sphere {
+            center: Vec3::new(3000., 0., -3000.),
+            radius: 2.,
+        });
+        assert_eq!(set.large, vec![sea]);
+        assert!(set.grid.len() as i64 <= MAX_VOLUME_CELLS);
+
+        assert_eq!(
+            set.containing_point(Vec3::new(3000., 0., -3000.)),
+            vec![sea, orb]
+        );
+        assert_eq!(
+            set.containing_point(Vec3::new(-9000., 5., 9000.)),
+            vec![sea]
+        );
+        assert!(set.containing_point(Vec3::new(0., 20., 0.)).is_empty());
+
+        let near =
+            set.overlapping_capsule(Vec3::new(-9000., 9., 0.), Vec3::new(-9000., 12., 0.), 1.);
+        assert_eq!(near, vec![sea]);
+
+        set.remove(sea);
+        assert!(set.large.is_empty());
+        assert_eq!(
+            set.containing_point(Vec3::new(3000., 0., -3000.)),
+            vec![orb]
+        );
+    }
+
+    #[test]
+    fn tracker_reports_enter_stay_exit() {
+        let mut set = VolumeSet::new();
+        let zone = set.add(Shape::Box {
+            center: Vec3::new(0., 1., 0.),
+            rotation: Quat::IDENTITY,
+            half: Vec3::new(2., 1., 2.),
+        });
+        let mut tracker = OverlapTracker::new();
+
+        let mut step = |x: f32| {
+            let a = Vec3::new(x, 0.5, 0.);
+            tracker.update(&set, a, a + Vec3::Y, 0.5)
+        };
+
+        assert!(step(-10.).is_empty());
+        assert_eq!(step(-2.2), vec![(zone, Overlap::Enter)]);
+        assert_eq!(step(0.), vec![(zone, Overlap::Stay)]);
+        assert_eq!(step(10.), vec![(zone, Overlap::Exit)]);
+        assert!(step(20.).is_empty());
+    }
+    fn capsule(x: f32, z: f32) -> ColliderShape {
+        ColliderShape::Capsule {
+            a: Vec3::new(x, 0.5, z),
+            b: Vec3::new(x, 1.5, z),
+            radius: 0.5,
+        }
+    }
+
+    #[test]
+    fn capsules_push_apart_by_mass() {
+        let mut set = ColliderSet::new();
+        set.add(0, Collider::solid(capsule(0., 0.), 1.));
+        set.add(1, Collider::solid(capsule(0.6, 0.), 3.));
+        set.add(2, Collider::solid(capsule(10., 0.), 1.));
+
+        assert_eq!(set.pairs(), vec![(0, 1)]);
+
+        let moves = set.separation();
+        assert_eq!(moves.len(), 2);
+        let (light, heavy) = (moves[0].1, moves[1].1);
+        assert_eq!(moves[0].0, 0);
+        assert!((light.x + 0.3).abs() < 1e-4);
+        assert!((heavy.x - 0.1).abs() < 1e-4);
+        assert!(light.y.abs() < 1e-4 && heavy.y.abs() < 1e-4);
+    }
+
+    #[test]
+    fn immovable_and_sensor_colliders() {
+        let mut set = ColliderSet::new();
+        set.add(
+            "crate",
+            Collider::solid(
+                ColliderShape::Aabb {
+                    min: Vec3::new(1., 0., -1.),
+                    max: Vec3::new(3., 2., 1.),
+                },
+                f32::INFINITY,
+            ),
+        );
+        set.add("player", Collider::solid(capsule(0.8, 0.), 1.));
+        set.add(
+            "coin",
+            Collider::sensor(ColliderShape::Sphere {
+                center: Vec3::new(-0.5, 1., 0.),
+                radius: 1.,
+            }),
+        );
+
+        let contacts = set.contacts();
+        assert_eq!(contacts.len(), 2);
+        let touched: Vec<_> = contacts
+            .iter()
+            .filter(|c| c.sensor)
+            .map(|c| (c.a, c.b))
+            .collect();
+        assert_eq!(touched, vec![("player", "coin")]);
+
+        // only the player moves, back out the near face
+        let moves = set.separation();
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].0, "player");
+        assert!((moves[0].1.x + 0.3).abs() < 1e-4);
+    }
+
+    #[test]
+    fn capsule_inside_box_leaves_by_nearest_face() {
+        let (normal, depth) = capsule(0., 0.9)
+            .contact(&ColliderShape::Aabb {
+                min: Vec3::new(-2., 0., -1.),
+                max: Vec3::new(2., 3., 1.),
+            })
+            .unwrap();
+        assert_eq!(normal, Vec3::Z);
+        assert!((depth - 0.6).abs() < 1e-4);
+    }
+
+    #[test]
+    fn slice_triangle_at_height() {
+        let tri = [
+            Vec3::new(0., 0., 0.),
+            Vec3::new(0., 3., 0.),
+            Vec3::new(3., 0., 0.),
+        ];
+
+        let (p1, p2) = triangle_slice_at_y(&tri, 1.).unwrap();
+        assert!(p1.distance(Vec2::new(0., 0.)) < 1e-5);
+        assert!(p2.distance(Vec2::new(2., 0.)) < 1e-5);
+
+        // flat bottom edge
+        assert_eq!(
+            triangle_slice_at_y(&tri, 0.),
+            Some((Vec2::new(3., 0.), Vec2::new(0., 0.)))
+        );
+        assert!(triangle_slice_at_y(&tri, 4.).is_none());
+    }
+
+    #[test]
+    fn segment_closest_point_clamps() {
+        let (a, b) = (Vec3::ZERO, Vec3::new(4., 0., 0.));
+        assert_eq!(
+            closest_point_on_segment_v3(Vec3::new(2., 3., 0.), a, b),
+            Vec3::new(2., 0., 0.)
+        );
+        assert_eq!(closest_point_on_segment_v3(Vec3::new(-2., 1., 0.), a, b), a);
+        assert_eq!(closest_point_on_segment_v3(Vec3::new(9., 0., 1.), a, b), b);
+    }
+
+    #[test]
+    fn circle_pushed_off_wall_front() {
+        let verts = [
+            Vec3::new(0., 0., 0.),
+            Vec3::new(0., 3., 0.),
+            Vec3::new(3., 0., 0.),
+        ];
+        let normal = get_face_normal(verts[0], verts[1], verts[2]);
+        assert_eq!(normal, Vec3::new(0., 0., -1.));
+
+        let Surface::Wall(wall) = Surface::new(verts, normal) else {
+            panic!("expected a wall");
+        };
+
+        let (push, _) = check_circle_tri_collision(Vec3::new(1., 1., -0.5), 1., &wall).unwrap();
+        assert!(push.distance(Vec3::new(0., 0., -0.5)) < 1e-5);
+
+        // behind, above, and out of reach
+        assert!(check_circle_tri_collision(Vec3::new(1., 1., 0.5), 1., &wall).is_none());
+        assert!(check_circle_tri_collision(Vec3::new(1., 4., -0.5), 1., &wall).is_none());
+        assert!(check_circle_tri_collision(Vec3::new(1., 1., -1.5), 1., &wall).is_none());
+    }
+
+    #[test]
+    fn ray_hits_capsule_from_either_side() {
+        let (a, b) = (Vec2::new(0., -1.), Vec2::new(0., 1.));
+
+        let (t, n) = ray_capsule_2d(Vec2::new(-3., 0.), Vec2::X, 10., a, b, 0.5).unwrap();
+        assert!((t - 2.5).abs() < 1e-5);
+        assert!(n.distance(Vec2::NEG_X) < 1e-5);
+
+        let (t, n) = ray_capsule_2d(Vec2::new(3., 0.5), Vec2::NEG_X, 10., a, b, 0.5).unwrap();
+        assert!((t - 2.5).abs() < 1e-5);
+        assert!(n.distance(Vec2::X) < 1e-5);
+
+        // end cap
+        let (t, n) = ray_capsule_2d(Vec2::new(0., -5.), Vec2::Y, 10., a, b, 0.5).unwrap();
+        assert!((t - 3.5).abs() < 1e-5);
+        assert!(n.distance(Vec2::NEG_Y) < 1e-5);
+
+        assert!(ray_capsule_2d(Vec2::new(-3., 0.), Vec2::X, 2., a, b, 0.5).is_none());
+        assert!(ray_capsule_2d(Vec2::new(-3., 5.), Vec2::X, 10., a, b, 0.5).is_none());
+
+        let (t, _) = ray_capsule_2d(Vec2::new(0.2, 0.), Vec2::X, 10., a, b, 0.5).unwrap();
+        assert_eq!(t, 0.);
+    }
+}
@@ -0,0 +1,499 @@
+// headless collision scenarios, a character is driven over procedurally
+// built geometry and checked against the level every tick
+use mcap::{
+    Affine3A, CharacterController, Quat, Surface, SurfaceBvh, SurfaceGrid, Vec3,
+    closest_point_triangle,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const DT: f32 = 1. / 60.;
+// how far past a surface a tick may end up
+const TOLERANCE: f32 = 0.01;
+// anything below this fell out of the level
+const KILL_Y: f32 = -50.;
+
+const HEIGHT: f32 = 2.7;
+const RADIUS: f32 = 1.;
+
+// what the character is told to do on a tick
+#[derive(Debug, Clone, Copy, Default)]
+struct Input {
+    wish: Vec3,
+    jump: bool,
+}
+
+fn walk(x: f32, z: f32) -> Input {
+    Input {
+        wish: Vec3::new(x, 0., z).normalize_or_zero(),
+        jump: false,
+    }
+}
+
+struct Scenario {
+    // checked against, and stepped on unless there's a bvh
+    grid: SurfaceGrid,
+    bvh: Option<SurfaceBvh>,
+    cc: CharacterController,
+    // most capsule casts any one step took
+    most_sweeps: u32,
+}
+
+impl Scenario {
+    fn new(surfaces: Vec<Surface>, start: Vec3) -> Self {
+        Self::on_grid(SurfaceGrid::new(surfaces), start)
+    }
+
+    fn on_grid(grid: SurfaceGrid, start: Vec3) -> Self {
+        Self {
+            grid,
+            bvh: None,
+            cc: CharacterController::new(start, HEIGHT, RADIUS),
+            most_sweeps: 0,
+        }
+    }
+
+    fn on_bvh(surfaces: Vec<Surface>, start: Vec3) -> Self {
+        Self {
+            bvh: Some(SurfaceBvh::new(surfaces.clone())),
+            ..Self::new(surfaces, start)
+        }
+    }
+
+    fn run(&mut self, ticks: usize, mut input: impl FnMut(usize) -> Input) {
+        for tick in 0..ticks {
+            let Input { wish, jump } = input(tick);
+            let report = match &self.bvh {
+                Some(bvh) => self.cc.step(DT, wish, jump, bvh),
+                None => self.cc.step(DT, wish, jump, &self.grid),
+            };
+            self.most_sweeps = self.most_sweeps.max(report.sweeps);
+            self.check(tick);
+        }
+    }
+
+    fn check(&self, tick: usize) {
+        let cc = &self.cc;
+        let pos = cc.position;
+        let chest = pos + Vec3::new(0., cc.chest_height(), 0.);
+        assert!(pos.is_finite(), "tick {tick}: position {pos}");
+        assert!(pos.y > KILL_Y, "tick {tick}: fell out at {pos}");
+
+        // walls are pushed out of at the chest
+        for s in self.grid.all_surfaces().unwrap_or_default() {
+            let Surface::Wall(t) = s else {
+                continue;
+            };
+            let d = closest_point_triangle(chest, &t.verts).distance(chest);
+            assert!(
+                d >= cc.radius - TOLERANCE,
+                "tick {tick}: inside wall {:?}, chest {chest} is {d} from it",
+                t.verts
+            );
+        }
+
+        // nothing walkable between the feet and the chest
+        if let Some(hit) = self.grid.raycast(chest, Vec3::NEG_Y, cc.chest_height()) {
+            let y = chest.y - hit.distance;
+            assert!(
+                pos.y >= y - TOLERANCE,
+                "tick {tick}: feet at {pos} below the floor at {y}"
+            );
+        }
+
+        // nor between the chest and the head
+        let head = cc.height - cc.chest_height();
+        if let Some(hit) = self.grid.raycast(chest, Vec3::Y, head) {
+            let y = chest.y + hit.distance;
+            assert!(
+                pos.y + cc.height <= y + TOLERANCE,
+                "tick {tick}: head at {} above the ceiling at {y}",
+                pos.y + cc.height
+            );
+        }
+    }
+}
+
+// two triangles, corners in order around the normal
+fn quad(c: [Vec3; 4], normal: Vec3) -> Vec<Surface> {
+    vec![
+        Surface::new([c[0], c[1], c[2]], normal),
+        Surface::new([c[0], c[2], c[3]], normal),
+    ]
+}
+
+fn floor(y: f32, min: (f32, f32), max: (f32, f32)) -> Vec<Surface> {
+    quad(
+        [
+            Vec3::new(min.0, y, min.1),
+            Vec3::new(min.0, y, max.1),
+            Vec3::new(max.0, y, max.1),
+            Vec3::new(max.0, y, min.1),
+        ],
+        Vec3::Y,
+    )
+}
+
+fn ceiling(y: f32, min: (f32, f32), max: (f32, f32)) -> Vec<Surface> {
+    quad(
+        [
+            Vec3::new(min.0, y, min.1),
+            Vec3::new(max.0, y, min.1),
+            Vec3::new(max.0, y, max.1),
+            Vec3::new(min.0, y, max.1),
+        ],
+        Vec3::NEG_Y,
+    )
+}
+
+// a wall from a to b on the ground, between heights y, facing normal
+fn wall(a: (f32, f32), b: (f32, f32), y: (f32, f32), normal: Vec3) -> Vec<Surface> {
+    quad(
+        [
+            Vec3::new(a.0, y.0, a.1),
+            Vec3::new(b.0, y.0, b.1),
+            Vec3::new(b.0, y.1, b.1),
+            Vec3::new(a.0, y.1, a.1),
+        ],
+        normal,
+    )
+}
+
+// wall from a to b, facing whichever side towards is on
+fn wall_towards(a: (f32, f32), b: (f32, f32), y: (f32, f32), towards: (f32, f32)) -> Vec<Surface> {
+    let along = Vec3::new(b.0 - a.0, 0., b.1 - a.1);
+    let mut normal = along.cross(Vec3::Y).normalize();
+    if normal.dot(Vec3::new(towards.0 - a.0, 0., towards.1 - a.1)) < 0. {
+        normal = -normal;
+    }
+    wall(a, b, y, normal)
+}
+
+// steps going up along +x from x = 0, 20 wide, with a landing past the top
+fn stairs(steps: usize, rise: f32, run: f32) -> Vec<Surface> {
+    let mut surfaces = floor(0., (-20., -10.), (0., 10.));
+    for i in 0..steps {
+        let (x, y) = (i as f32 * run, i as f32 * rise);
+        surfaces.extend(wall((x, 10.), (x, -10.), (y, y + rise), Vec3::NEG_X));
+        surfaces.extend(floor(y + rise, (x, -10.), (x + run, 10.)));
+    }
+    let (x, y) = (steps as f32 * run, steps as f32 * rise);
+    surfaces.extend(floor(y, (x, -10.), (x + 40., 10.)));
+    surfaces
+}
+
+// a slope rising along +x from x = 0 up to height, with floors either side
+fn ramp(degrees: f32, height: f32) -> Vec<Surface> {
+    let angle = degrees.to_radians();
+    let run = height / angle.tan();
+    let normal = Vec3::new(-angle.sin(), angle.cos(), 0.);
+
+    let mut surfaces = floor(0., (-20., -10.), (0., 10.));
+    surfaces.extend(quad(
+        [
+            Vec3::new(0., 0., -10.),
+            Vec3::new(0., 0., 10.),
+            Vec3::new(run, height, 10.),
+            Vec3::new(run, height, -10.),
+        ],
+        normal,
+    ));
+    surfaces.extend(floor(height, (run, -10.), (run + 40., 10.)));
+    surfaces
+}
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 0.05
+}
+
+#[test]
+fn walks_across_flat_floor() {
+    let mut s = Scenario::new(floor(0., (-40., -40.), (40., 40.)), Vec3::ZERO);
+    s.run(120, |_| walk(1., 0.));
+
+    // accel / friction is 10 units a second, less the first tenth of a second
+    // getting up to speed
+    assert!(s.cc.on_ground);
+    assert!(close(s.cc.position.y, 0.), "{}", s.cc.position);
+    assert!((17.0..21.0).contains(&s.cc.position.x), "{}", s.cc.position);
+    assert!(close(s.cc.position.z, 0.), "{}", s.cc.position);
+}
+
+#[test]
+fn settles_into_square_corner() {
+    let mut surfaces = floor(0., (-20., -20.), (20., 20.));
+    surfaces.extend(wall((3., -20.), (3., 20.), (0., 5.), Vec3::NEG_X));
+    surfaces.extend(wall((-20., 3.), (20., 3.), (0., 5.), Vec3::NEG_Z));
+    let mut s = Scenario::new(surfaces, Vec3::ZERO);
+
+    s.run(180, |_| walk(1., 1.));
+
+    let expected = 3. - RADIUS;
+    assert!(close(s.cc.position.x, expected), "{}", s.cc.position);
+    assert!(close(s.cc.position.z, expected), "{}", s.cc.position);
+}
+
+#[test]
+fn settles_into_acute_corner() {
+    // a wedge narrowing towards +x, meeting at (6, 0)
+    let mut surfaces = floor(0., (-20., -20.), (20., 20.));
+    surfaces.extend(wall_towards((-6., -3.), (6., 0.), (0., 5.), (0., 0.)));
+    surfaces.extend(wall_towards((-6., 3.), (6., 0.), (0., 5.), (0., 0.)));
+    let mut s = Scenario::new(surfaces, Vec3::new(-4., 0., 0.));
+
+    s.run(240, |_| walk(1., 0.));
+
+    // held off both walls, so at radius / sin(half angle) short of the tip
+    let half_angle = (3f32 / 12.).atan();
+    let expected = 6. - RADIUS / half_angle.sin();
+    let (x, z) = (s.cc.position.x, s.cc.position.z);
+    assert!((x - expected).abs() < 0.1, "{}", s.cc.position);
+    assert!(z.abs() < 0.1, "{}", s.cc.position);
+}
+
+#[test]
+fn climbs_stairs() {
+    let mut s = Scenario::new(stairs(6, 0.5, 1.5), Vec3::new(-4., 0., 0.));
+    s.run(240, |_| walk(1., 0.));
+
+    assert!(s.cc.on_ground);
+    assert!(s.cc.position.x > 9., "{}", s.cc.position);
+    assert!(close(s.cc.position.y, 3.), "{}", s.cc.position);
+}
+
+#[test]
+fn blocked_by_step_taller_than_snap_up() {
+    let mut s = Scenario::new(stairs(1, 1.5, 4.), Vec3::new(-4., 0., 0.));
+    s.run(180, |_| walk(1., 0.));
+
+    assert!(s.cc.on_ground);
+    assert!(close(s.cc.position.y, 0.), "{}", s.cc.position);
+    assert!(s.cc.position.x < 0., "{}", s.cc.position);
+}
+
+#[test]
+fn walks_up_gentle_ramp() {
+    let mut s = Scenario::new(ramp(30., 4.), Vec3::new(-4., 0., 0.));
+    s.run(240, |_| walk(1., 0.));
+
+    assert!(s.cc.on_ground);
+    assert!(close(s.cc.position.y, 4.), "{}", s.cc.position);
+}
+
+#[test]
+fn walks_up_slope_in_few_sweeps() {
+    let mut s = Scenario::new(ramp(30., 4.), Vec3::new(-4., 0., 0.));
+    s.run(240, |_| walk(1., 0.));
+
+    assert!(close(s.cc.position.y, 4.), "{}", s.cc.position);
+    assert!(s.most_sweeps <= 2, "{}", s.most_sweeps);
+}
+
+#[test]
+fn pushes_into_wall_in_few_sweeps() {
+    let mut surfaces = floor(0., (-20., -20.), (20., 20.));
+    surfaces.extend(wall((3., -20.), (3., 20.), (0., 5.), Vec3::NEG_X));
+    let mut s = Scenario::new(surfaces, Vec3::ZERO);
+    s.run(180, |_| walk(1., 0.3));
+
+    assert!(close(s.cc.position.x, 3. - RADIUS), "{}", s.cc.position);
+    assert!(s.cc.position.z > 1., "{}", s.cc.position);
+    assert!(s.most_sweeps <= 2, "{}", s.most_sweeps);
+}
+
+#[test]
+fn shove_stops_at_wall_and_slides() {
+    let mut surfaces = floor(0., (-20., -20.), (20., 20.));
+    surfaces.extend(wall((3., -20.), (3., 20.), (0., 5.), Vec3::NEG_X));
+    let mut s = Scenario::new(surfaces, Vec3::ZERO);
+
+    s.cc.shove(Vec3::new(5., 0., 1.), &s.grid);
+
+    assert!(s.cc.position.x < 3. - RADIUS + 0.01, "{}", s.cc.position);
+    assert!(close(s.cc.position.z, 1.), "{}", s.cc.position);
+    assert!(close(s.cc.position.y, 0.), "{}", s.cc.position);
+}
+
+#[test]
+fn cant_climb_steep_slope() {
+    let mut s = Scenario::new(ramp(70., 4.), Vec3::new(-4., 0., 0.));
+    s.run(240, |_| walk(1., 0.));
+
+    assert!(s.cc.position.y < 1., "{}", s.cc.position);
+    assert!(s.cc.position.x < 1., "{}", s.cc.position);
+}
+
+#[test]
+fn slides_off_steep_slope() {
+    // start halfway up
+    let start = Vec3::new(2. / 70f32.to_radians().tan(), 2., 0.);
+    let mut s = Scenario::new(ramp(70., 4.), start);
+    s.run(180, |_| Input::default());
+
+    assert!(s.cc.on_ground);
+    assert!(close(s.cc.position.y, 0.), "{}", s.cc.position);
+    assert!(s.cc.position.x < 0., "{}", s.cc.position);
+}
+
+#[test]
+fn ceiling_stops_jump() {
+    let mut surfaces = floor(0., (-20., -20.), (20., 20.));
+    surfaces.extend(ceiling(HEIGHT + 1., (-20., -20.), (20., 20.)));
+    let mut s = Scenario::new(surfaces, Vec3::ZERO);
+
+    let mut highest: f32 = 0.;
+    for _ in 0..60 {
+        s.run(1, |_| Input {
+            wish: Vec3::ZERO,
+            jump: true,
+        });
+        highest = highest.max(s.cc.position.y);
+    }
+    s.run(60, |_| Input::default());
+
+    assert!(highest > 0.5, "never left the ground");
+    assert!(s.cc.on_ground);
+    assert!(close(s.cc.position.y, 0.), "{}", s.cc.position);
+}
+
+// walled room with stairs, a pillar and a low ceiling
+fn room() -> Vec<Surface> {
+    let mut surfaces = stairs(4, 0.5, 1.5);
+    surfaces.extend(wall((-20., -10.), (-20., 10.), (0., 8.), Vec3::X));
+    surfaces.extend(wall((26., 10.), (26., -10.), (0., 8.), Vec3::NEG_X));
+    surfaces.extend(wall((-20., 10.), (26., 10.), (0., 8.), Vec3::NEG_Z));
+    surfaces.extend(wall((26., -10.), (-20., -10.), (0., 8.), Vec3::Z));
+    surfaces.extend(wall((-10., -2.), (-10., 2.), (0., 8.), Vec3::NEG_X));
+    surfaces.extend(wall((-10., 2.), (-8., 2.), (0., 8.), Vec3::Z));
+    surfaces.extend(wall((-8., 2.), (-8., -2.), (0., 8.), Vec3::X));
+    surfaces.extend(wall((-8., -2.), (-10., -2.), (0., 8.), Vec3::NEG_Z));
+    surfaces.extend(ceiling(HEIGHT + 0.5, (-18., 4.), (-12., 10.)));
+    surfaces
+}
+
+const ROOM_START: Vec3 = Vec3::new(-15., 0., -5.);
+
+fn random_walk(s: &mut Scenario) {
+    let mut rng = StdRng::seed_from_u64(47);
+    let mut input = Input::default();
+    s.run(3600, |tick| {
+        // a new direction every half second
+        if tick % 30 == 0 {
+            input = walk(rng.random_range(-1.0..1.0), rng.random_range(-1.0..1.0));
+        }
+        Input {
+            jump: rng.random_bool(0.02),
+            ..input
+        }
+    });
+}
+
+#[test]
+fn random_walk_in_room() {
+    random_walk(&mut Scenario::new(room(), ROOM_START));
+}
+
+#[test]
+fn random_walk_in_room_with_large_cells() {
+    let grid = SurfaceGrid::with_cell_size(room(), 12.);
+    random_walk(&mut Scenario::on_grid(grid, ROOM_START));
+}
+
+#[test]
+fn random_walk_in_room_on_bvh() {
+    random_walk(&mut Scenario::on_bvh(room(), ROOM_START));
+}
+
+#[test]
+fn settles_into_acute_corner_on_bvh() {
+    let mut surfaces = floor(0., (-20., -20.), (20., 20.));
+    surfaces.extend(wall_towards((-6., -3.), (6., 0.), (0., 5.), (0., 0.)));
+    surfaces.extend(wall_towards((-6., 3.), (6., 0.), (0., 5.), (0., 0.)));
+    let mut grid = Scenario::new(surfaces.clone(), Vec3::new(-4., 0., 0.));
+    let mut bvh = Scenario::on_bvh(surfaces, Vec3::new(-4., 0., 0.));
+
+    grid.run(240, |_| walk(1., 0.));
+    bvh.run(240, |_| walk(1., 0.));
+
+    // the same surfaces, found in a different order
+    let (a, b) = (grid.cc.position, bvh.cc.position);
+    assert!(a.distance(b) < TOLERANCE, "{a} on the grid, {b} on the bvh");
+}
+
+// a platform 8 wide, its top at y = 1 wherever it's moved to
+fn platform(s: &mut Scenario) -> mcap::BodyId {
+    s.grid.add_body(floor(1., (-4., -4.), (4., 4.)))
+}
+
+#[test]
+fn carried_by_moving_platform_and_keeps_its_speed_off_it() {
+    let mut s = Scenario::new(vec![], Vec3::new(0., 1., 0.));
+    let id = platform(&mut s);
+    let speed = 2.;
+    // stand on it before it moves
+    s.run(1, |_| Input::default());
+
+    let mut x = 0.;
+    for _ in 0..120 {
+        x += speed * DT;
+        let to = glam::Vec3::new(x, 0., 0.);
+        s.grid
+            .set_body_transform(id, Affine3A::from_translation(to));
+        s.run(1, |_| Input::default());
+    }
+
+    // stood still on it, so it's where the platform took it
+    assert!(s.cc.on_ground);
+    assert_eq!(s.cc.floor_body, Some(id));
+    assert!(
+        close(s.cc.position.x, x),
+        "{} with the platform at {x}",
+        s.cc.position
+    );
+    assert!(close(s.cc.position.y, 1.), "{}", s.cc.position);
+
+    x += speed * DT;
+    let to = glam::Vec3::new(x, 0., 0.);
+    s.grid
+        .set_body_transform(id, Affine3A::from_translation(to));
+    s.run(1, |_| Input {
+        wish: Vec3::ZERO,
+        jump: true,
+    });
+
+    assert!(!s.cc.on_ground);
+    assert_eq!(s.cc.floor_body, None);
+    assert!(close(s.cc.velocity.x, speed), "{}", s.cc.velocity);
+
+    // the platform stops, the jump still carries on forward
+    let start = s.cc.position.x;
+    s.run(10, |_| Input::default());
+    assert!(
+        s.cc.position.x > start + 0.1,
+        "{} from {start}",
+        s.cc.position
+    );
+}
+
+#[test]
+fn carried_round_spinning_platform() {
+    let mut s = Scenario::new(vec![], Vec3::new(2., 1., 0.));
+    let id = platform(&mut s);
+    s.run(1, |_| Input::default());
+
+    // a quarter turn a second, for a second
+    for tick in 1..=60 {
+        let angle = std::f32::consts::FRAC_PI_2 * tick as f32 / 60.;
+        let turned = Affine3A::from_quat(Quat::from_rotation_y(angle));
+        s.grid.set_body_transform(id, turned);
+        s.run(1, |_| Input::default());
+    }
+
+    assert!(s.cc.on_ground);
+    let expected = Vec3::new(0., 1., -2.);
+    assert!(
+        s.cc.position.distance(expected) < 0.05,
+        "{} not {expected}",
+        s.cc.position
+    );
+}
@@ -10,6 +10,9 @@ pub mod exports {
 
 use msgpacker::prelude::*;
 
+// bump whenever Payload changes, maps packed with any other version are refused
+pub const FORMAT_VERSION: u32 = 1;
+
 pub fn marshal(
     version: u32,
     floats: &Vec<f32>,
@@ -19,6 +22,8 @@ pub fn marshal(
     frame_data: &Vec<String>,
     map_ref_entt: &Vec<external::EntityReference>,
     map_ins_entt: &Vec<external::EntityInstance>,
+    materials: &Vec<external::SurfaceMaterial>,
+    collision: &external::Collision,
 ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
     let p = internal::Payload {
         version,
@@ -37,10 +42,13 @@ pub fn marshal(
             .map(|e| internal::EntityReference {
                 name: e.name,
                 is_decor: e.is_decor,
+                no_collide: e.no_collide,
+                no_draw: e.no_draw,
                 frame_names: e.frame_names,
                 texture: e.texture,
                 vertices: e.vertices,
                 uvs: e.uvs,
+                materials: e.materials,
             })
             .collect(),
         map_ins_ents: map_ins_entt
@@ -55,6 +63,40 @@ pub fn marshal(
                 scale: e.scale,
             })
             .collect(),
+        materials: materials
+            .iter()
+            .map(|m| internal::SurfaceMaterial {
+                name: m.name,
+                friction: m.friction,
+                slide_angle: m.slide_angle,
+                ice: m.ice,
+                mud: m.mud,
+                bouncy: m.bouncy,
+                sound: m.sound,
+            })
+            .collect(),
+        collision: internal::Collision {
+            grid_size: collision.grid_size,
+            instances: collision.instances.clone(),
+            surfaces: collision
+                .surfaces
+                .iter()
+                .map(|s| internal::CollisionSurface {
+                    verts: s.verts.clone(),
+                    normal: s.normal.clone(),
+                    kind: s.kind,
+                    material: s.material,
+                })
+                .collect(),
+            cells: collision
+                .cells
+                .iter()
+                .map(|c| internal::CollisionCell {
+                    cell: c.cell.clone(),
+                    surfaces: c.surfaces.clone(),
+                })
+                .collect(),
+        },
     };
 
     let mut buf = vec![];
@@ -65,6 +107,17 @@ pub fn marshal(
 }
 
 pub fn unmarshal(buf: &Vec<u8>) -> Result<external::Payload, Box<dyn std::error::Error>> {
+    // version leads the payload, checked first since an older layout
+    // won't unpack at all
+    let (_, version) =
+        u32::unpack(buf).map_err(|_| external::MparseError("failed to read version"))?;
+    if version != FORMAT_VERSION {
+        return Err(Box::new(external::VersionError {
+            found: version,
+            expected: FORMAT_VERSION,
+        }));
+    }
+
     let (_, t) =
         internal::Payload::unpack(&buf).map_err(|_| external::MparseError("failed to read_str"))?;
     let p = external::Payload {
@@ -80,10 +133,13 @@ pub fn unmarshal(buf: &Vec<u8>) -> Result<external::Payload, Box<dyn std::error:
             .map(|e| external::EntityReference {
                 name: e.name,
                 is_decor: e.is_decor,
+                no_collide: e.no_collide,
+                no_draw: e.no_draw,
                 frame_names: e.frame_names,
                 texture: e.texture,
                 vertices: e.vertices,
                 uvs: e.uvs,
+                materials: e.materials,
             })
             .collect(),
         map_ins_ents: t
@@ -99,6 +155,43 @@ pub fn unmarshal(buf: &Vec<u8>) -> Result<external::Payload, Box<dyn std::error:
                 scale: e.scale,
             })
             .collect(),
+        materials: t
+            .materials
+            .into_iter()
+            .map(|m| external::SurfaceMaterial {
+                name: m.name,
+                friction: m.friction,
+                slide_angle: m.slide_angle,
+                ice: m.ice,
+                mud: m.mud,
+                bouncy: m.bouncy,
+                sound: m.sound,
+            })
+            .collect(),
+        collision: external::Collision {
+            grid_size: t.collision.grid_size,
+            instances: t.collision.instances,
+            surfaces: t
+                .collision
+                .surfaces
+                .into_iter()
+                .map(|s| external::CollisionSurface {
+                    verts: s.verts,
+                    normal: s.normal,
+                    kind: s.kind,
+                    material: s.material,
+                })
+                .collect(),
+            cells: t
+                .collision
+                .cells
+                .into_iter()
+                .map(|c| external::CollisionCell {
+                    cell: c.cell,
+                    surfaces: c.surfaces,
+                })
+                .collect(),
+        },
     };
     Ok(p)
 }
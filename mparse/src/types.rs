@@ -4,10 +4,15 @@ pub mod internal {
     pub struct EntityReference {
         pub name: u32,
         pub is_decor: bool,
+        // decor only, set from the reference's glTF extras
+        pub no_collide: bool,
+        pub no_draw: bool,
         pub frame_names: Vec<u32>,
         pub texture: u32,
         pub vertices: Vec<Vec<u32>>,
         pub uvs: Vec<u32>,
+        // decor only, one per triangle, u32 -> materials
+        pub materials: Vec<u32>,
     }
 
     #[derive(Debug, MsgPacker, PartialEq, Clone)]
@@ -21,6 +26,44 @@ pub mod internal {
         pub scale: u32,       // u32 -> [f32;3]
     }
 
+    #[derive(Debug, MsgPacker, PartialEq, Clone)]
+    pub struct SurfaceMaterial {
+        pub name: u32, // u32 -> kvs
+        pub friction: f32,
+        // degrees, 0 for the game's default
+        pub slide_angle: f32,
+        pub ice: bool,
+        pub mud: bool,
+        pub bouncy: bool,
+        pub sound: u32, // u32 -> kvs
+    }
+
+    // static decor collision in world space, baked by nmcc
+    #[derive(Debug, MsgPacker, PartialEq, Clone)]
+    pub struct CollisionSurface {
+        pub verts: Vec<f32>,  // [x,y,z; 3]
+        pub normal: Vec<f32>, // [x,y,z]
+        // 0 wall, 1 floor, 2 slide, 3 ceiling
+        pub kind: u8,
+        pub material: u32, // u32 -> materials
+    }
+
+    #[derive(Debug, MsgPacker, PartialEq, Clone)]
+    pub struct CollisionCell {
+        pub cell: Vec<i32>,     // [x,y,z]
+        pub surfaces: Vec<u32>, // u32 -> surfaces
+    }
+
+    #[derive(Debug, MsgPacker, PartialEq, Clone)]
+    pub struct Collision {
+        // the cell size the grid was bucketed with
+        pub grid_size: f32,
+        // map instances whose meshes are in surfaces
+        pub instances: Vec<u32>,
+        pub surfaces: Vec<CollisionSurface>,
+        pub cells: Vec<CollisionCell>,
+    }
+
     #[derive(Debug, MsgPacker, PartialEq)]
     pub struct DecorInstance {
         pub index: u32,
@@ -39,6 +82,8 @@ pub mod internal {
         pub fn_data: Vec<String>,
         pub map_ref_ents: Vec<EntityReference>,
         pub map_ins_ents: Vec<EntityInstance>,
+        pub materials: Vec<SurfaceMaterial>,
+        pub collision: Collision,
     }
 }
 
@@ -52,14 +97,35 @@ pub mod external {
     }
     impl<'a> std::error::Error for MparseError<'a> {}
 
+    #[derive(Debug)]
+    pub struct VersionError {
+        pub found: u32,
+        pub expected: u32,
+    }
+    impl std::fmt::Display for VersionError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(
+                f,
+                "map format version {}, expected {}, rebuild the map with nmcc",
+                self.found, self.expected
+            )
+        }
+    }
+    impl std::error::Error for VersionError {}
+
     #[derive(Debug, Clone)]
     pub struct EntityReference {
         pub name: u32,
         pub is_decor: bool,
+        // decor only, set from the reference's glTF extras
+        pub no_collide: bool,
+        pub no_draw: bool,
         pub frame_names: Vec<u32>,
         pub texture: u32,
         pub vertices: Vec<Vec<u32>>,
         pub uvs: Vec<u32>,
+        // decor only, one per triangle, u32 -> materials
+        pub materials: Vec<u32>,
     }
 
     #[derive(Debug, Clone)]
@@ -73,6 +139,44 @@ pub mod external {
         pub scale: u32,       // u32 -> [f32;3]
     }
 
+    #[derive(Debug, PartialEq, Clone)]
+    pub struct SurfaceMaterial {
+        pub name: u32, // u32 -> kvs
+        pub friction: f32,
+        // degrees, 0 for the game's default
+        pub slide_angle: f32,
+        pub ice: bool,
+        pub mud: bool,
+        pub bouncy: bool,
+        pub sound: u32, // u32 -> kvs
+    }
+
+    // static decor collision in world space, baked by nmcc
+    #[derive(Debug, PartialEq, Clone)]
+    pub struct CollisionSurface {
+        pub verts: Vec<f32>,  // [x,y,z; 3]
+        pub normal: Vec<f32>, // [x,y,z]
+        // 0 wall, 1 floor, 2 slide, 3 ceiling
+        pub kind: u8,
+        pub material: u32, // u32 -> materials
+    }
+
+    #[derive(Debug, PartialEq, Clone)]
+    pub struct CollisionCell {
+        pub cell: Vec<i32>,     // [x,y,z]
+        pub surfaces: Vec<u32>, // u32 -> surfaces
+    }
+
+    #[derive(Debug, PartialEq, Clone)]
+    pub struct Collision {
+        // the cell size the grid was bucketed with
+        pub grid_size: f32,
+        // map instances whose meshes are in surfaces
+        pub instances: Vec<u32>,
+        pub surfaces: Vec<CollisionSurface>,
+        pub cells: Vec<CollisionCell>,
+    }
+
     #[derive(Debug, PartialEq)]
     pub struct DecorInstance {
         pub index: u32,
@@ -91,5 +195,7 @@ pub mod external {
         pub fn_data: Vec<String>,
         pub map_ref_ents: Vec<EntityReference>,
         pub map_ins_ents: Vec<EntityInstance>,
+        pub materials: Vec<SurfaceMaterial>,
+        pub collision: Collision,
     }
 }
@@ -47,3 +47,17 @@ pub fn load_sfx() -> Result<(), NUError> {
 pub fn load_mus() -> Result<(), NUError> {
     Ok(())
 }
+
+// plays a loaded sound effect once, names that aren't loaded are
+// ignored as most things don't have sounds yet
+pub fn play_sfx(name: &str) -> Result<(), NUError> {
+    let ag = AudioGod::get()?;
+    let Some(chunk) = ag.sfx_map.get(name) else {
+        return Ok(());
+    };
+
+    sdl2::mixer::Channel::all()
+        .play(chunk, 0)
+        .map_err(NUError::SDLError)?;
+    Ok(())
+}
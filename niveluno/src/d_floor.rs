@@ -1,3 +1,4 @@
+use crate::g_instance::Instance;
 use crate::map::Entity;
 
 use crate::g_game;
@@ -27,13 +28,19 @@ impl Floor {
             mat: matrix,
         }
     }
+}
+
+impl Instance for Floor {
+    fn is_decor(&self) -> bool {
+        true
+    }
 
-    pub fn update(&mut self) {
+    fn update(&mut self) {
         // self.base.location[1] += 0.001;
         // eprintln!("floor pos: {:?}", self.base.location);
     }
 
-    pub fn draw_model(&mut self) {
+    fn draw_model(&mut self) {
         let ref_dec = g_game::get_ref_entity(self.base.ref_id).unwrap();
 
         let dc = render::DrawCall {
@@ -48,12 +55,17 @@ impl Floor {
         render::draw(dc).unwrap();
     }
 
-    pub fn get_mesh(&self) -> Vec<[raymath::Vector3; 3]> {
+    fn get_mesh(&self) -> Option<Vec<[raymath::Vector3; 3]>> {
         let ldr = g_game::get_ref_entity(self.base.ref_id).unwrap();
-        ldr.mesh
+        Some(ldr.mesh)
     }
 
-    pub fn get_matrix(&self) -> raymath::Matrix {
-        self.mat
+    fn get_matrix(&self) -> Option<raymath::Matrix> {
+        Some(self.mat)
+    }
+
+    fn get_materials(&self) -> Option<Vec<mcap::Material>> {
+        let ldr = g_game::get_ref_entity(self.base.ref_id).unwrap();
+        Some(ldr.materials)
     }
 }
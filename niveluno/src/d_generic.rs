@@ -1,4 +1,5 @@
-use crate::map::Entity;
+use crate::g_instance::Instance;
+use crate::map::{Entity, LoadedEnttReference};
 
 use crate::g_game;
 use crate::render;
@@ -6,10 +7,12 @@ use crate::render;
 pub struct Generic {
     base: Entity,
     mat: raymath::Matrix,
+    no_collide: bool,
+    no_draw: bool,
 }
 
 impl Generic {
-    pub fn new(decor: &Entity) -> Self {
+    pub fn new(decor: &Entity, reference: &LoadedEnttReference) -> Self {
         // scale, rotation, translation
         let mat_s = raymath::matrix_scale(decor.scale[0], decor.scale[1], decor.scale[2]);
         let mat_r = raymath::quaternion_to_matrix(decor.rotation.into());
@@ -25,12 +28,22 @@ impl Generic {
         Self {
             base: decor.clone(),
             mat: matrix,
+            no_collide: reference.no_collide,
+            no_draw: reference.no_draw,
         }
     }
+}
+
+impl Instance for Generic {
+    fn is_decor(&self) -> bool {
+        true
+    }
 
-    pub fn update(&mut self) {}
+    fn draw_model(&mut self) {
+        if self.no_draw {
+            return;
+        }
 
-    pub fn draw_model(&mut self) {
         let ref_dec = g_game::get_ref_entity(self.base.ref_id).unwrap();
 
         let dc = render::DrawCall {
@@ -45,12 +58,25 @@ impl Generic {
         render::draw(dc).unwrap();
     }
 
-    pub fn get_mesh(&self) -> Vec<[raymath::Vector3; 3]> {
+    fn get_mesh(&self) -> Option<Vec<[raymath::Vector3; 3]>> {
+        if self.no_collide {
+            return None;
+        }
+
         let ldr = g_game::get_ref_entity(self.base.ref_id).unwrap();
-        ldr.mesh
+        Some(ldr.mesh)
     }
 
-    pub fn get_matrix(&self) -> raymath::Matrix {
-        self.mat
+    fn get_matrix(&self) -> Option<raymath::Matrix> {
+        Some(self.mat)
+    }
+
+    fn get_materials(&self) -> Option<Vec<mcap::Material>> {
+        if self.no_collide {
+            return None;
+        }
+
+        let ldr = g_game::get_ref_entity(self.base.ref_id).unwrap();
+        Some(ldr.materials)
     }
 }
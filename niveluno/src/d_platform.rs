@@ -1,27 +1,118 @@
+use mcap::BodyId;
+use raymath::{vector3_add, vector3_length, vector3_scale, vector3_subtract, Vector3};
+
+use crate::g_instance::Instance;
 use crate::map::Entity;
+use crate::math::{matrix_to_affine, mesh_surfaces};
+use crate::nuerror::NUError;
 
 use crate::g_game;
 use crate::render;
+use crate::time;
 
 pub struct Platform {
     base: Entity,
     mat: raymath::Matrix,
+    // offsets from the placed location, visited in order
+    // then back to the start, empty if it doesn't travel
+    path: Vec<Vector3>,
+    // units per second along path
+    speed: f32,
+    // radians per second around y
+    spin: f32,
+    // index into the loop of [0, 0, 0] + path being headed for
+    leg: usize,
+    offset: Vector3,
+    yaw: f32,
+    // registered on the first update, once the grid exists
+    body: Option<BodyId>,
 }
 
 impl Platform {
-    pub fn new(decor: &Entity) -> Self {
+    pub fn new(decor: &Entity) -> Result<Self, NUError> {
+        let path = decor.get_vec3_list("path")?;
+        let speed = decor.get_float("speed", 4.)?;
+        if !path.is_empty() && speed <= 0. {
+            return Err(decor.param_error("speed", "must be above 0"));
+        }
+
         // todo, scale + quat
-        Self {
+        Ok(Self {
             base: decor.clone(),
             mat: raymath::matrix_translate(decor.location[0], decor.location[1], decor.location[2]),
+            path,
+            speed,
+            spin: decor.get_float("spin", 0.)?.to_radians(),
+            leg: 1,
+            offset: Vector3::new(0., 0., 0.),
+            yaw: 0.,
+            body: None,
+        })
+    }
+
+    fn moves(&self) -> bool {
+        !self.path.is_empty() || self.spin != 0.
+    }
+
+    fn leg_point(&self, leg: usize) -> Vector3 {
+        match leg {
+            0 => Vector3::new(0., 0., 0.),
+            i => self.path[i - 1],
         }
     }
-    pub fn update(&mut self) {
-        // self.base.location[1] += 0.001;
-        // eprintln!("floor pos: {:?}", self.base.location);
+
+    fn travel(&mut self, mut dist: f32) {
+        let legs = self.path.len() + 1;
+
+        // bounded, so a path of identical points can't spin forever
+        for _ in 0..legs {
+            let to_target = vector3_subtract(self.leg_point(self.leg), self.offset);
+            let remaining = vector3_length(to_target);
+
+            if remaining > dist {
+                self.offset = vector3_add(self.offset, vector3_scale(to_target, dist / remaining));
+                return;
+            }
+
+            self.offset = self.leg_point(self.leg);
+            self.leg = (self.leg + 1) % legs;
+            dist -= remaining;
+        }
+    }
+}
+
+impl Instance for Platform {
+    fn is_decor(&self) -> bool {
+        true
+    }
+
+    fn update(&mut self) {
+        if !self.moves() {
+            return;
+        }
+
+        let grid = g_game::get_surface_grid_mut().unwrap();
+        let body = *self.body.get_or_insert_with(|| {
+            let ldr = g_game::get_ref_entity(self.base.ref_id).unwrap();
+            grid.add_body(mesh_surfaces(&ldr.mesh, &ldr.materials))
+        });
+
+        let fd = time::get_delta_time().unwrap() as f32;
+        if !self.path.is_empty() {
+            self.travel(self.speed * fd);
+        }
+        self.yaw = (self.yaw + self.spin * fd) % (2. * std::f32::consts::PI);
+
+        let position = vector3_add(self.base.location.into(), self.offset);
+        self.mat = raymath::matrix_multiply(
+            raymath::matrix_rotate_y(self.yaw),
+            raymath::matrix_translate(position.x, position.y, position.z),
+        );
+
+        grid.set_body_transform(body, matrix_to_affine(self.mat));
     }
 
-    pub fn draw_model(&mut self) {
+    fn draw_model(&mut self) {
         let ref_dec = g_game::get_ref_entity(self.base.ref_id).unwrap();
 
         let dc = render::DrawCall {
@@ -36,12 +127,24 @@ impl Platform {
         render::draw(dc).unwrap();
     }
 
-    pub fn get_mesh(&self) -> Vec<[raymath::Vector3; 3]> {
+    // moving platforms are bodies, not part of the static grid
+    fn get_mesh(&self) -> Option<Vec<[raymath::Vector3; 3]>> {
+        if self.moves() {
+            return None;
+        }
         let ldr = g_game::get_ref_entity(self.base.ref_id).unwrap();
-        ldr.mesh
+        Some(ldr.mesh)
     }
 
-    pub fn get_matrix(&self) -> raymath::Matrix {
-        self.mat
+    fn get_matrix(&self) -> Option<raymath::Matrix> {
+        Some(self.mat)
+    }
+
+    fn get_materials(&self) -> Option<Vec<mcap::Material>> {
+        if self.moves() {
+            return None;
+        }
+        let ldr = g_game::get_ref_entity(self.base.ref_id).unwrap();
+        Some(ldr.materials)
     }
 }
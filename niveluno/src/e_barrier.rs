@@ -1,35 +1,30 @@
 use core::f32;
 
 use raymath::{
-    Matrix, Vector3, matrix_identity, quaternion_to_matrix, vector3_add, vector3_distance, vector3_transform
+    Matrix, Vector3, matrix_identity, quaternion_to_matrix, vector3_add, vector3_transform
 };
 
-use crate::{e_player, g_instance, math, time};
+use crate::math::ToVec3;
+use crate::{g_instance, math, time};
 
+use crate::g_instance::Instance;
 use crate::map::Entity;
+use crate::nuerror::NUError;
 
 use crate::text::{BannerInput, FontColor, OverlaySurface};
 use crate::{g_game, render, text};
 
 pub struct Barrier {
     base: Entity,
-    id: Option<u32>,
-    bounds: Bounds,
+    id: u32,
+    volume: mcap::Shape,
     mats: [Matrix; 8],
     v_text: Box<OverlaySurface>,
     tex_id: u32,
 }
 
-struct Bounds {
-    b1: Vector3,
-    b2: Vector3,
-    b3: Vector3,
-    b4: Vector3,
-    t1: Vector3,
-    t2: Vector3,
-    t3: Vector3,
-    t4: Vector3,
-}
+// todo, add player.height * 2. or something too big to jump above
+const BARRIER_HEIGHT: f32 = 2.;
 
 const BANNER_COLORS_V3: [[f32; 3]; 11] = [
     [0.0, 0.0, 0.5],
@@ -60,17 +55,17 @@ const BANNER_COLORS_RGB: [[u8; 3]; 11] = [
 ];
 
 impl Barrier {
-    pub fn new(entt: &Entity) -> Self {
-        let mut id = None;
-
-        for (i, v) in entt.params.iter().enumerate() {
-            let key = g_game::get_param(*v as usize).unwrap();
-            if key == "id" {
-                let value = g_game::get_param(entt.params[i + 1] as usize).unwrap();
-                id = Some(value.parse().unwrap());
-                break;
-            }
+    pub fn new(entt: &Entity) -> Result<Self, NUError> {
+        // the id picks the banner color and the level shown on it
+        if entt.get_param("id")?.is_none() {
+            return Err(entt.param_error("id", "is required"));
+        }
+        let id = entt.get_int("id", 0)?;
+        if id < 0 || id as usize >= BANNER_COLORS_RGB.len() {
+            let msg = format!("{id} is not in 0..{}", BANNER_COLORS_RGB.len());
+            return Err(entt.param_error("id", &msg));
         }
+        let id = id as u32;
 
         let mat_r = quaternion_to_matrix(entt.rotation.into());
 
@@ -82,11 +77,10 @@ impl Barrier {
             vector3_transform(Vector3::new( entt.scale[0] / 2., 0., -entt.scale[2] / 2.), mat_r),
             vector3_transform(Vector3::new( entt.scale[0] / 2., 0.,  entt.scale[2] / 2.), mat_r),
             // tops
-            // todo, add player.height * 2. or something too big to jump above
-            vector3_add(vector3_transform( Vector3::new(-entt.scale[0] / 2., 0., -entt.scale[2] / 2.), mat_r), Vector3::new(0., 2., 0.)),
-            vector3_add(vector3_transform( Vector3::new(-entt.scale[0] / 2., 0.,  entt.scale[2] / 2.), mat_r), Vector3::new(0., 2., 0.)),
-            vector3_add(vector3_transform( Vector3::new( entt.scale[0] / 2., 0., -entt.scale[2] / 2.), mat_r), Vector3::new(0., 2., 0.)),
-            vector3_add(vector3_transform( Vector3::new( entt.scale[0] / 2., 0.,  entt.scale[2] / 2.), mat_r), Vector3::new(0., 2., 0.)),
+            vector3_add(vector3_transform( Vector3::new(-entt.scale[0] / 2., 0., -entt.scale[2] / 2.), mat_r), Vector3::new(0., BARRIER_HEIGHT, 0.)),
+            vector3_add(vector3_transform( Vector3::new(-entt.scale[0] / 2., 0.,  entt.scale[2] / 2.), mat_r), Vector3::new(0., BARRIER_HEIGHT, 0.)),
+            vector3_add(vector3_transform( Vector3::new( entt.scale[0] / 2., 0., -entt.scale[2] / 2.), mat_r), Vector3::new(0., BARRIER_HEIGHT, 0.)),
+            vector3_add(vector3_transform( Vector3::new( entt.scale[0] / 2., 0.,  entt.scale[2] / 2.), mat_r), Vector3::new(0., BARRIER_HEIGHT, 0.)),
         ];
 
         let mut mats = [matrix_identity(); 8];
@@ -102,7 +96,16 @@ impl Barrier {
             *point = raymath::vector3_transform(*point, mat_t);
         }
 
-        let color = BANNER_COLORS_RGB[id.unwrap() as usize];
+        // the footprint, BARRIER_HEIGHT tall
+        let rotation = mcap::Quat::from_array(entt.rotation);
+        let volume = mcap::Shape::Box {
+            center: mcap::Vec3::from_array(entt.location)
+                + rotation * mcap::Vec3::new(0., BARRIER_HEIGHT / 2., 0.),
+            rotation,
+            half: mcap::Vec3::new(entt.scale[0], BARRIER_HEIGHT, entt.scale[2]) / 2.,
+        };
+
+        let color = BANNER_COLORS_RGB[id as usize];
 
         let v_text = text::create_barrier_level_surface(BannerInput {
             color: FontColor {
@@ -111,33 +114,34 @@ impl Barrier {
                 b: color[2],
                 a: 255,
             },
-            level: id.unwrap() * 10,
+            level: id * 10,
         })
         .unwrap();
 
         let pixels = v_text.surf.without_lock().unwrap();
         let tex_id = render::create_texture_from_rgba(pixels, v_text.w, v_text.h).unwrap() as u32;
 
-        Self {
+        Ok(Self {
             base: entt.clone(),
             id,
-            bounds: Bounds {
-                b1: bounds[0],
-                b2: bounds[1],
-                b3: bounds[2],
-                b4: bounds[3],
-                t1: bounds[4],
-                t2: bounds[5],
-                t3: bounds[6],
-                t4: bounds[7],
-            },
+            volume,
             mats,
             v_text,
             tex_id,
-        }
+        })
+    }
+
+    pub fn position_is_inside(&self, point: Vector3) -> bool {
+        self.volume.contains_point(point.to_mcapv3())
     }
 
-    pub fn update(&mut self) {
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+}
+
+impl Instance for Barrier {
+    fn update(&mut self) {
 
         render::push_billboard(
             self.base.location.into(),
@@ -150,7 +154,7 @@ impl Barrier {
         
     }
 
-    pub fn draw_model(&mut self) {
+    fn draw_model(&mut self) {
         let ref_ent = g_game::get_ref_entity(self.base.ref_id).unwrap();
 
         let mat_s =
@@ -167,7 +171,7 @@ impl Barrier {
         mat = raymath::matrix_multiply(mat, mat_r);
         mat = raymath::matrix_multiply(mat, mat_t);
 
-        let color = BANNER_COLORS_V3[self.id.unwrap() as usize];
+        let color = BANNER_COLORS_V3[self.id as usize];
 
         let cam_pos = render::get_camera_pos().unwrap();
         let cam_light_dist = math::vector3_distance(self.base.location.into(), cam_pos);
@@ -213,48 +217,4 @@ impl Barrier {
             render::push_debug_circle(floating_center, 0.5, axis, f32::consts::PI, [1., 1., 1., 1.]).unwrap();
         }
     }
-
-    pub fn position_is_inside(&self, point: Vector3) -> bool {
-        let t1 = [self.bounds.t1, self.bounds.t2, self.bounds.t3];
-        let t2 = [self.bounds.t2, self.bounds.t3, self.bounds.t4];
-
-        let rt = math::Ray {
-            position: point,
-            direction: Vector3::new(0., 1., 0.),
-        };
-
-        if !math::get_ray_collision_triangle(rt, t1[0], t1[1], t1[2]).hit
-            && !math::get_ray_collision_triangle(rt, t2[0], t2[1], t2[2]).hit
-        {
-            return false;
-        }
-
-        let b1 = [self.bounds.b1, self.bounds.b2, self.bounds.b3];
-        let b2 = [self.bounds.b2, self.bounds.b3, self.bounds.b4];
-
-        let rb = math::Ray {
-            position: point,
-            direction: Vector3::new(0., -1., 0.),
-        };
-
-        if !math::get_ray_collision_triangle(rb, b1[0], b1[1], b1[2]).hit
-            && !math::get_ray_collision_triangle(rb, b2[0], b2[1], b2[2]).hit
-        {
-            return false;
-        }
-
-        true
-    }
-
-    pub fn get_id(&self) -> u32 {
-        self.id.unwrap()
-    }
-
-    pub fn get_mesh(&self) -> Vec<[raymath::Vector3; 3]> {
-        panic!("don't fetch entity meshes")
-    }
-
-    pub fn get_matrix(&self) -> raymath::Matrix {
-        panic!("don't fetch entity meshes")
-    }
 }
@@ -0,0 +1,48 @@
+use crate::g_instance::Instance;
+use crate::map::Entity;
+use crate::{g_game, g_instance};
+
+pub struct Checkpoint {
+    base: Entity,
+    // None until the first update, so a player
+    // loaded inside the volume doesn't re-save
+    was_inside: Option<bool>,
+}
+
+impl Checkpoint {
+    pub fn new(entt: &Entity) -> Self {
+        Self {
+            base: entt.clone(),
+            was_inside: None,
+        }
+    }
+}
+
+impl Instance for Checkpoint {
+    fn update(&mut self) {
+        let Ok((position, yaw)) =
+            g_instance::with_player(|player| (player.position, player.get_yaw()))
+        else {
+            return;
+        };
+
+        let inside = self.base.contains_point(position);
+        let entered = inside && self.was_inside == Some(false);
+        self.was_inside = Some(inside);
+
+        if !entered || g_game::get_state().unwrap() != g_game::TopState::Play {
+            return;
+        }
+
+        g_game::set_checkpoint(position, yaw).unwrap();
+
+        match g_game::save_game() {
+            Ok(_) => g_game::push_notice("CHECKPOINT").unwrap(),
+            Err(e) => eprintln!("E: checkpoint failed to save: {e}"),
+        }
+    }
+
+    fn draw_model(&mut self) {
+        self.base.draw_debug_volume([0., 1., 0., 1.]);
+    }
+}
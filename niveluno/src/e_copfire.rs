@@ -3,6 +3,7 @@ use core::f32;
 use rand::Rng;
 use raymath::{vector3_add, vector3_scale};
 
+use crate::g_instance::Instance;
 use crate::map::Entity;
 use crate::math::Vector3;
 
@@ -58,8 +59,10 @@ impl CopFire {
             current_time: 0.,
         }
     }
+}
 
-    pub fn update(&mut self) {
+impl Instance for CopFire {
+    fn update(&mut self) {
 
         self.current_time = time::get_run_time().unwrap();
 
@@ -78,7 +81,7 @@ impl CopFire {
 
     }
 
-    pub fn draw_model(&mut self) {
+    fn draw_model(&mut self) {
 
         let re = g_instance::ref_ent_from_str("icosphere").unwrap();
 
@@ -141,12 +144,4 @@ impl CopFire {
 
         }
     }
-
-    pub fn get_mesh(&self) -> Vec<[raymath::Vector3; 3]> {
-        panic!("don't fetch entity meshes")
-    }
-
-    pub fn get_matrix(&self) -> raymath::Matrix {
-        panic!("don't fetch entity meshes")
-    }
 }
@@ -2,6 +2,7 @@ use core::f32;
 
 use raymath::{vector3_add, vector3_scale};
 
+use crate::g_instance::Instance;
 use crate::map::Entity;
 use crate::math::Vector3;
 
@@ -17,7 +18,10 @@ impl CopLight {
             base: entt.clone(),
         }
     }
-    pub fn update(&mut self) {
+}
+
+impl Instance for CopLight {
+    fn update(&mut self) {
 
         let run_time = time::get_run_time().unwrap();
         let speed = 10.;
@@ -34,14 +38,4 @@ impl CopLight {
 
         render::push_light(position, 64, 0, 0, 255).unwrap();
     }
-
-    pub fn draw_model(&mut self) {}
-
-    pub fn get_mesh(&self) -> Vec<[raymath::Vector3; 3]> {
-        panic!("don't fetch entity meshes")
-    }
-
-    pub fn get_matrix(&self) -> raymath::Matrix {
-        panic!("don't fetch entity meshes")
-    }
 }
@@ -1,3 +1,4 @@
+use crate::g_instance::Instance;
 use crate::map::Entity;
 use crate::time;
 
@@ -47,7 +48,10 @@ impl Gcyl {
             quat: raymath::quaternion_identity(),
         }
     }
-    pub fn update(&mut self) {
+}
+
+impl Instance for Gcyl {
+    fn update(&mut self) {
         let dt = time::get_delta_time().unwrap() as f32;
 
         self.yaw += 1. * dt;
@@ -61,7 +65,7 @@ impl Gcyl {
         self.quat = raymath::quaternion_multiply(self.quat, quat_y);
     }
 
-    pub fn draw_model(&mut self) {
+    fn draw_model(&mut self) {
         let ref_ent = g_game::get_ref_entity(self.base.ref_id).unwrap();
 
         let mat_r = raymath::quaternion_to_matrix(self.quat);
@@ -89,12 +93,4 @@ impl Gcyl {
         };
         render::draw(dc).unwrap();
     }
-
-    pub fn get_mesh(&self) -> Vec<[raymath::Vector3; 3]> {
-        panic!("don't fetch entity meshes")
-    }
-
-    pub fn get_matrix(&self) -> raymath::Matrix {
-        panic!("don't fetch entity meshes")
-    }
 }
@@ -0,0 +1,52 @@
+use crate::g_game::{self, Event};
+use crate::g_instance::Instance;
+use crate::map::Entity;
+use crate::nuerror::NUError;
+use crate::g_instance;
+
+// hurts the player every tick they're inside it,
+// the player's invulnerability frames space out the hits
+pub struct Hazard {
+    base: Entity,
+    damage: u32,
+}
+
+impl Hazard {
+    pub fn new(entt: &Entity) -> Result<Self, NUError> {
+        let damage = entt.get_int("damage", 1)?;
+        if damage <= 0 {
+            return Err(entt.param_error("damage", "must be above 0"));
+        }
+
+        Ok(Self {
+            base: entt.clone(),
+            damage: damage as u32,
+        })
+    }
+}
+
+impl Instance for Hazard {
+    fn update(&mut self) {
+        let Ok(player) = g_instance::with_player(|player| player.position) else {
+            return;
+        };
+
+        if !self.base.contains_point(player) {
+            return;
+        }
+
+        let Ok(Some(target)) = g_game::get_player_handle() else {
+            return;
+        };
+        g_game::emit(Event::Damaged {
+            target,
+            amount: self.damage,
+            source: None,
+        })
+        .unwrap();
+    }
+
+    fn draw_model(&mut self) {
+        self.base.draw_debug_volume([1., 0., 0., 1.]);
+    }
+}
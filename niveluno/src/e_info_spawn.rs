@@ -0,0 +1,55 @@
+use raymath::{vector3_add, vector3_rotate_by_quaternion, vector3_scale, Vector3};
+
+use crate::g_instance::Instance;
+use crate::map::Entity;
+use crate::nuerror::NUError;
+use crate::{g_game, render};
+
+pub struct InfoSpawn {
+    pub name: Option<String>,
+    pub position: Vector3,
+    pub yaw: f32,
+}
+
+impl InfoSpawn {
+    pub fn new(entt: &Entity) -> Result<Self, NUError> {
+        let name = entt.get_param("name")?.map(str::to_string);
+
+        // facing is the entity's rotated +z, flattened onto the player's yaw,
+        // matrix_rotate_y(yaw) takes +z to (sin(yaw), 0, cos(yaw))
+        let forward = vector3_rotate_by_quaternion(Vector3::new(0., 0., 1.), entt.rotation.into());
+        let yaw = forward.x.atan2(forward.z);
+
+        Ok(Self {
+            name,
+            position: entt.location.into(),
+            yaw,
+        })
+    }
+
+    pub fn matches(&self, name: Option<&str>) -> bool {
+        match name {
+            Some(n) => self.name.as_deref() == Some(n),
+            None => true,
+        }
+    }
+}
+
+impl Instance for InfoSpawn {
+    fn draw_model(&mut self) {
+        if cfg!(debug_assertions) && g_game::get_state().unwrap() != g_game::TopState::Menu {
+            let forward = Vector3::new(self.yaw.sin(), 0., self.yaw.cos());
+            let base = vector3_add(self.position, Vector3::new(0., 0.1, 0.));
+            render::push_debug_point(base, 0., 1., 1., 1.).unwrap();
+            render::push_debug_line(
+                base,
+                vector3_add(base, vector3_scale(forward, 1.)),
+                0.,
+                1.,
+                1.,
+                1.,
+            )
+            .unwrap();
+        }
+    }
+}
@@ -1,9 +1,11 @@
 use raymath::{vector3_add, vector3_scale};
 
+use crate::g_instance::Instance;
 use crate::map::Entity;
 use crate::math::Vector3;
+use crate::nuerror::NUError;
 
-use crate::{g_game, render, time};
+use crate::{render, time};
 
 pub struct Light {
     _base: Entity,
@@ -13,6 +15,8 @@ pub struct Light {
     intensity: u8,
     orientation: Orientation,
     position: Vector3,
+    // toggled by activate
+    on: bool,
 }
 
 #[derive(PartialEq)]
@@ -20,45 +24,27 @@ enum Orientation {
     LeftRight,
     UpDown,
     BackForward,
-    Unset,
 }
 
 impl Light {
-    pub fn new(entt: &Entity) -> Self {
+    pub fn new(entt: &Entity) -> Result<Self, NUError> {
         // let ref_ent = g_game::get_ref_entity(entt.index).unwrap();
 
         // eprintln!("re.names: {:?}", ref_ent.frame_names);
 
-        let mut rgbi = [1, 128, 255, 255];
-
-        for (i, v) in entt.params.iter().enumerate() {
-            let key = g_game::get_param(*v as usize).unwrap();
-            if key == "color" {
-                let value = g_game::get_param(entt.params[i + 1] as usize).unwrap();
-                let str_rgbi: Vec<&str> = value.split(',').collect();
-                rgbi[0] = str_rgbi[0].parse().unwrap();
-                rgbi[1] = str_rgbi[1].parse().unwrap();
-                rgbi[2] = str_rgbi[2].parse().unwrap();
-                rgbi[3] = str_rgbi[3].parse().unwrap();
-                break;
-            }
-        }
+        // the 4th component is intensity
+        let rgbi = entt.get_color("color", [1, 128, 255, 255])?;
 
-        let mut orientation = Orientation::LeftRight;
-        for (i, v) in entt.params.iter().enumerate() {
-            let key = g_game::get_param(*v as usize).unwrap();
-            if key == "orientation" {
-                let value = g_game::get_param(entt.params[i + 1] as usize).unwrap();
-                orientation = match value {
-                    "lr" => Orientation::LeftRight,
-                    "ud" => Orientation::UpDown,
-                    "bf" => Orientation::BackForward,
-                    _ => Orientation::Unset,
-                }
-            }
-        }
+        let orientation = match entt.get_string("orientation", "lr")?.as_str() {
+            "lr" => Orientation::LeftRight,
+            "ud" => Orientation::UpDown,
+            "bf" => Orientation::BackForward,
+            _ => return Err(entt.param_error("orientation", "must be lr, ud or bf")),
+        };
 
-        Self {
+        let on = entt.get_bool("on", true)?;
+
+        Ok(Self {
             _base: entt.clone(),
             r: rgbi[0],
             g: rgbi[1],
@@ -66,23 +52,8 @@ impl Light {
             intensity: rgbi[3],
             orientation,
             position: entt.location.into(),
-        }
-    }
-    pub fn update(&mut self) {
-        // if self.orientation != Orientation::Unset {
-        //     self.move_on_orientation()
-        // }
-
-        render::push_light(self.position, self.intensity, self.r, self.g, self.b).unwrap();
-    }
-    pub fn draw_model(&mut self) {}
-
-    pub fn get_mesh(&self) -> Vec<[raymath::Vector3; 3]> {
-        panic!("don't fetch entity meshes")
-    }
-
-    pub fn get_matrix(&self) -> raymath::Matrix {
-        panic!("don't fetch entity meshes")
+            on,
+        })
     }
 
     fn move_on_orientation(&mut self) {
@@ -111,9 +82,6 @@ impl Light {
                 },
                 3.,
             ),
-            Orientation::Unset => {
-                unreachable!("light had unset orientation in move_on_orientation")
-            }
         };
 
         let time_factor = (time::get_run_time().unwrap() + shift).sin() as f32;
@@ -125,3 +93,19 @@ impl Light {
         );
     }
 }
+
+impl Instance for Light {
+    fn update(&mut self) {
+        // self.move_on_orientation();
+
+        if !self.on {
+            return;
+        }
+
+        render::push_light(self.position, self.intensity, self.r, self.g, self.b).unwrap();
+    }
+
+    fn activate(&mut self) {
+        self.on = !self.on;
+    }
+}
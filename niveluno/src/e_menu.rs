@@ -1,6 +1,8 @@
 use core::f32;
 
+use crate::g_instance::Instance;
 use crate::map::Entity;
+use crate::nuerror::NUError;
 use crate::time;
 
 use crate::g_game;
@@ -15,19 +17,33 @@ pub struct Menu {
     pitch: f32,
     scale_mat: raymath::Matrix,
     quat: raymath::Quaternion,
+    // where starting the game goes, any one menu entity can carry these
+    map: Option<String>,
+    spawn: Option<String>,
 }
 
 impl Menu {
-    pub fn new(entt: &Entity) -> Self {
-        Self {
+    pub fn new(entt: &Entity) -> Result<Self, NUError> {
+        Ok(Self {
             base: entt.clone(),
             yaw: f32::consts::PI,
             pitch: 0.,
             quat: raymath::quaternion_identity(),
             scale_mat: raymath::matrix_scale(entt.scale[0], entt.scale[1], entt.scale[2]),
-        }
+            map: entt.get_param("map")?.map(str::to_string),
+            spawn: entt.get_param("spawn")?.map(str::to_string),
+        })
     }
-    pub fn update(&mut self) {
+
+    // (map, spawn) if this one says where the game starts
+    pub fn start(&self) -> Option<(String, Option<String>)> {
+        let map = self.map.clone()?;
+        Some((map, self.spawn.clone()))
+    }
+}
+
+impl Instance for Menu {
+    fn update(&mut self) {
         let time = time::get_run_time().unwrap() as f32;
 
         self.yaw = time.sin() as f32 / 2.;
@@ -41,7 +57,7 @@ impl Menu {
         self.quat = raymath::quaternion_multiply(self.quat, quat_p);
     }
 
-    pub fn draw_model(&mut self) {
+    fn draw_model(&mut self) {
         let ref_ent = g_game::get_ref_entity(self.base.ref_id).unwrap();
 
         let mat_r = raymath::quaternion_to_matrix(self.quat);
@@ -67,12 +83,4 @@ impl Menu {
         };
         render::draw(dc).unwrap();
     }
-
-    pub fn get_mesh(&self) -> Vec<[raymath::Vector3; 3]> {
-        panic!("don't fetch entity meshes")
-    }
-
-    pub fn get_matrix(&self) -> raymath::Matrix {
-        panic!("don't fetch entity meshes")
-    }
 }
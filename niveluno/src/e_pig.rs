@@ -1,22 +1,34 @@
-use crate::map::Entity;
-use crate::time;
+use raymath::{vector3_scale, Vector3};
 
-use crate::g_game;
-use crate::render;
+use crate::g_game::{self, Event};
+use crate::g_instance::{self, Instance};
+use crate::map::Entity;
+use crate::math::{ToVec3, ToVector3};
+use crate::nuerror::NUError;
+use crate::{render, time};
 
 pub struct Pig {
     base: Entity,
+    position: Vector3,
     yaw: f32,
-    pitch: f32,
+    // position is copied in and out around each step
+    controller: mcap::CharacterController,
     scale_mat: raymath::Matrix,
     quat: raymath::Quaternion,
     animations: Vec<Vec<usize>>,
     anim_id: PigAnimations,
     anim_time: f32,
     anim_length: Vec<f32>,
+    state: PigState,
+    // set once the current swipe has landed or missed
+    swipe_resolved: bool,
+    health: i32,
+    sight_range: f32,
+    attack_range: f32,
+    damage: u32,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 #[repr(usize)]
 enum PigAnimations {
     Tpose,
@@ -30,6 +42,50 @@ enum PigAnimations {
     __End,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PigState {
+    Idle,
+    Chase,
+    Swipe,
+    Bump,
+    Fall,
+    Land,
+    Die,
+    Dead,
+}
+
+impl PigState {
+    fn animation(self) -> PigAnimations {
+        match self {
+            PigState::Idle => PigAnimations::Tpose,
+            PigState::Chase => PigAnimations::Charge,
+            PigState::Swipe => PigAnimations::Swipe,
+            PigState::Bump => PigAnimations::Bump,
+            PigState::Fall => PigAnimations::Drop,
+            PigState::Land => PigAnimations::Land,
+            PigState::Die => PigAnimations::Die,
+            PigState::Dead => PigAnimations::Dead,
+        }
+    }
+
+    // looping animations wrap, the rest hold their last frame
+    fn looping(self) -> bool {
+        matches!(
+            self,
+            PigState::Idle | PigState::Chase | PigState::Fall | PigState::Dead
+        )
+    }
+}
+
+const SNAP_UP: f32 = 1.;
+// players further above/below than this aren't noticed
+const SIGHT_HEIGHT: f32 = 4.;
+// chase gives up past sight_range * this
+const LOSE_SIGHT_FACTOR: f32 = 1.5;
+// how far into the swipe the hit is checked, 0..1
+const SWIPE_HIT_AT: f32 = 0.5;
+const MASS: f32 = 2.;
+
 const PIG_FRAME_DEFAULT: &'static str = "default";
 
 const PIG_FRAME_SWIPE_000: &'static str = "swipe.000";
@@ -160,89 +216,295 @@ const PIG_ANIMATIONS: &[&[&str]] = &[
 ];
 
 impl Pig {
-    pub fn new(entt: &Entity) -> Self {
-        let ref_ent = g_game::get_ref_entity(entt.ref_id).unwrap();
+    pub fn new(entt: &Entity) -> Result<Self, NUError> {
+        let ref_ent = g_game::get_ref_entity(entt.ref_id)?;
         let animations = g_game::get_animation_ids(PIG_ANIMATIONS, &ref_ent);
 
-        eprintln!("re.names: {:?}", ref_ent.frame_names);
-        eprintln!("re.animations: {:?}", animations);
-
+        // seconds per frame
         let mut anim_length = vec![0.; PigAnimations::__End as usize];
-        eprintln!("anim_length.len() {}", anim_length.len());
         anim_length[PigAnimations::Tpose as usize] = 1.0;
-        anim_length[PigAnimations::Swipe as usize] = 0.1;
-        anim_length[PigAnimations::Charge as usize] = 0.2;
+        anim_length[PigAnimations::Swipe as usize] = 0.05;
+        anim_length[PigAnimations::Charge as usize] = 0.1;
         anim_length[PigAnimations::Drop as usize] = 0.4;
-        anim_length[PigAnimations::Land as usize] = 0.4;
-        anim_length[PigAnimations::Die as usize] = 0.15;
+        anim_length[PigAnimations::Land as usize] = 0.06;
+        anim_length[PigAnimations::Die as usize] = 0.08;
         anim_length[PigAnimations::Dead as usize] = 1.0;
-        anim_length[PigAnimations::Bump as usize] = 0.15;
+        anim_length[PigAnimations::Bump as usize] = 0.05;
 
         assert_eq!(animations.len(), anim_length.len());
 
-        Self {
+        let health = entt.get_int("health", 3)?;
+        if health <= 0 {
+            return Err(entt.param_error("health", "must be above 0"));
+        }
+        let damage = entt.get_int("damage", 1)?;
+        if damage < 0 {
+            return Err(entt.param_error("damage", "can't be negative"));
+        }
+        let radius = entt.get_float("radius", 1.)?;
+        if radius <= 0. {
+            return Err(entt.param_error("radius", "must be above 0"));
+        }
+
+        let position: Vector3 = entt.location.into();
+        let speed = entt.get_float("speed", 8.)?;
+
+        let mut controller = mcap::CharacterController::new(
+            position.to_mcapv3(),
+            SNAP_UP * 0.7 + 2. * radius,
+            radius,
+        );
+        // accelerate to walking speed quickly, pigs don't jump
+        controller.accel = speed * controller.friction;
+        controller.jump_speed = 0.;
+
+        Ok(Self {
             base: entt.clone(),
+            position,
             yaw: 0.,
-            pitch: 0.,
+            controller,
             scale_mat: raymath::matrix_scale(entt.scale[0], entt.scale[1], entt.scale[2]),
-            quat: raymath::quaternion_identity(),
+            quat: entt.rotation.into(),
             animations,
-            anim_id: PigAnimations::Swipe,
+            anim_id: PigAnimations::Tpose,
             anim_time: 0.,
             anim_length,
+            state: PigState::Idle,
+            swipe_resolved: false,
+            health,
+            sight_range: entt.get_float("sight_range", 20.)?,
+            attack_range: entt.get_float("attack_range", 2.5)?,
+            damage: damage as u32,
+        })
+    }
+
+    fn set_state(&mut self, state: PigState) {
+        self.state = state;
+        self.anim_id = state.animation();
+        self.anim_time = 0.;
+        self.swipe_resolved = false;
+    }
+
+    fn anim_duration(&self) -> f32 {
+        self.animations[self.anim_id as usize].len() as f32
+            * self.anim_length[self.anim_id as usize]
+    }
+
+    fn anim_finished(&self) -> bool {
+        !self.state.looping() && self.anim_time >= self.anim_duration()
+    }
+
+    // current frame, next frame and the mix between them
+    fn anim_frames(&self) -> (usize, usize, f32) {
+        let anim = &self.animations[self.anim_id as usize];
+        let f = self.anim_time / self.anim_length[self.anim_id as usize];
+        let i = f as usize;
+
+        if self.state.looping() {
+            (
+                anim[i % anim.len()],
+                anim[(i + 1) % anim.len()],
+                f - f.floor(),
+            )
+        } else if i + 1 >= anim.len() {
+            let last = anim[anim.len() - 1];
+            (last, last, 0.)
+        } else {
+            (anim[i], anim[i + 1], f - f.floor())
         }
     }
-    pub fn update(&mut self) {
-        let _ = self.yaw;
-        let _ = self.pitch;
 
-        self.quat = self.base.rotation.into();
+    // None once it's dying, there's nothing left to hit
+    pub fn chest(&self) -> Option<Vector3> {
+        if matches!(self.state, PigState::Die | PigState::Dead) {
+            return None;
+        }
+        let eye = Vector3::new(0., self.controller.chest_height(), 0.);
+        Some(raymath::vector3_add(self.position, eye))
     }
 
-    pub fn draw_model(&mut self) {
-        let ref_ent = g_game::get_ref_entity(self.base.ref_id).unwrap();
+    // horizontal offset to the player and its length, if the player is
+    // within range, not too far above or below and not behind a wall
+    fn player_offset(&self, range: f32) -> Option<(Vector3, f32)> {
+        let (player, player_chest) =
+            g_instance::with_player(|player| (player.position, player.chest())).ok()?;
+        let d = raymath::vector3_subtract(player, self.position);
+        if d.y.abs() > SIGHT_HEIGHT {
+            return None;
+        }
 
-        // scale, rotation, translation
-        let mat_r = raymath::quaternion_to_matrix(self.quat);
-        let mat_t = raymath::matrix_translate(
-            self.base.location[0],
-            self.base.location[1],
-            self.base.location[2],
-        );
+        let flat = Vector3::new(d.x, 0., d.z);
+        let dist = raymath::vector3_length(flat);
+        if dist > range {
+            return None;
+        }
 
-        let mut mat = raymath::matrix_identity();
-        mat = raymath::matrix_multiply(mat, self.scale_mat);
-        mat = raymath::matrix_multiply(mat, mat_r);
-        mat = raymath::matrix_multiply(mat, mat_t);
+        // chest to chest, pig to player
+        let from = self.chest()?.to_mcapv3();
+        let to = player_chest.to_mcapv3() - from;
+        let grid = g_game::get_surface_grid().ok()?;
+        if grid.raycast(from, to, to.length()).is_some() {
+            return None;
+        }
 
-        // animation, frame, mix
-        self.anim_time += time::get_delta_time().unwrap() as f32;
+        Some((flat, dist))
+    }
 
-        let f = self.anim_time / self.anim_length[self.anim_id as usize];
-        let mut mix = f - f.floor();
+    fn face(&mut self, dir: Vector3) {
+        if dir.x.abs() > f32::EPSILON || dir.z.abs() > f32::EPSILON {
+            self.yaw = dir.x.atan2(dir.z);
+        }
+    }
 
-        let anim = &self.animations[self.anim_id as usize];
+    fn try_hit_player(&mut self) -> Result<(), NUError> {
+        self.swipe_resolved = true;
+
+        if self.player_offset(self.attack_range).is_none() {
+            return Ok(());
+        }
+        let Some(target) = g_game::get_player_handle()? else {
+            return Ok(());
+        };
+
+        g_game::emit(Event::Damaged {
+            target,
+            amount: self.damage,
+            source: None,
+        })
+    }
+
+    fn think(&mut self) -> Result<(), NUError> {
+        let mut wish = Vector3::new(0., 0., 0.);
 
-        let frame_curr = anim[(f as usize) % anim.len()];
-        let mut frame_next = anim[((f as usize) + 1) % anim.len()];
-        // if frame_next < frame_curr {
-        //     let tmp = frame_curr;
-        //     frame_curr = frame_next;
-        //     frame_next = tmp;
-        //     mix = 1. - mix;
-        // }
-        if frame_next < frame_curr || ((frame_next == frame_curr) && f >= 1.0) {
-            let mut next_id = (self.anim_id as usize) + 1;
-            if next_id >= PigAnimations::__End as usize {
-                next_id = 1;
+        match self.state {
+            PigState::Idle => {
+                if let Some((d, _)) = self.player_offset(self.sight_range) {
+                    self.face(d);
+                    self.set_state(PigState::Chase);
+                }
             }
-            self.anim_id = unsafe { std::mem::transmute(next_id) };
+            PigState::Chase => match self.player_offset(self.sight_range * LOSE_SIGHT_FACTOR) {
+                Some((d, dist)) if dist <= self.attack_range => {
+                    self.face(d);
+                    self.set_state(PigState::Swipe);
+                }
+                Some((d, dist)) => {
+                    self.face(d);
+                    wish = vector3_scale(d, 1. / dist);
+                }
+                None => self.set_state(PigState::Idle),
+            },
+            PigState::Swipe => {
+                if !self.swipe_resolved && self.anim_time >= self.anim_duration() * SWIPE_HIT_AT {
+                    self.try_hit_player()?;
+                }
+                if self.anim_finished() {
+                    self.set_state(PigState::Chase);
+                }
+            }
+            PigState::Bump | PigState::Land => {
+                if self.anim_finished() {
+                    self.set_state(PigState::Chase);
+                }
+            }
+            PigState::Fall => {
+                if self.controller.on_ground {
+                    self.set_state(PigState::Land);
+                }
+            }
+            PigState::Die => {
+                if self.anim_finished() {
+                    self.set_state(PigState::Dead);
+                }
+            }
+            PigState::Dead => return Ok(()),
+        }
+
+        let bumped = self.update_physics(wish)?;
 
-            frame_next = self.animations[self.anim_id as usize][0];
-            mix = 0.5;
+        if !self.controller.on_ground && self.state != PigState::Fall && self.state != PigState::Die
+        {
+            self.set_state(PigState::Fall);
+        } else if bumped && self.state == PigState::Chase {
+            self.set_state(PigState::Bump);
+        }
+
+        Ok(())
+    }
+
+    // returns true if a wall was hit
+    fn update_physics(&mut self, wish: Vector3) -> Result<bool, NUError> {
+        let fd = time::get_delta_time()? as f32;
 
-            self.anim_time = 0.;
+        self.controller.position = self.position.to_mcapv3();
+        let report = self
+            .controller
+            .step(fd, wish.to_mcapv3(), false, g_game::get_surface_grid()?);
+        self.position = self.controller.position.to_rayv3();
+
+        Ok(report.hit_wall)
+    }
+}
+
+impl Instance for Pig {
+    fn update(&mut self) {
+        self.anim_time += time::get_delta_time().unwrap() as f32;
+
+        if let Err(e) = self.think() {
+            eprintln!("E: pig update failed: {e}");
         }
+    }
+
+    fn on_event(&mut self, event: &Event) {
+        // only ever delivered to the target
+        let Event::Damaged { amount, .. } = event else {
+            return;
+        };
+        if matches!(self.state, PigState::Die | PigState::Dead) {
+            return;
+        }
+
+        self.health -= *amount as i32;
+        if self.health <= 0 {
+            self.set_state(PigState::Die);
+        } else {
+            self.set_state(PigState::Bump);
+        }
+    }
+
+    fn get_collider(&self) -> Option<mcap::Collider> {
+        if matches!(self.state, PigState::Die | PigState::Dead) {
+            return None;
+        }
+        let shape = self.controller.collider_shape();
+        Some(mcap::Collider::solid(shape, MASS))
+    }
+
+    fn push(&mut self, offset: Vector3) {
+        let Ok(grid) = g_game::get_surface_grid() else {
+            return;
+        };
+        self.controller.position = self.position.to_mcapv3();
+        self.controller
+            .shove(Vector3::new(offset.x, 0., offset.z).to_mcapv3(), grid);
+        self.position = self.controller.position.to_rayv3();
+    }
+
+    fn draw_model(&mut self) {
+        let ref_ent = g_game::get_ref_entity(self.base.ref_id).unwrap();
+
+        // scale, rotation, facing, translation
+        let mat_r = raymath::quaternion_to_matrix(self.quat);
+        let mat_y = raymath::matrix_rotate_y(self.yaw);
+        let mat_t = raymath::matrix_translate(self.position.x, self.position.y, self.position.z);
+
+        let mut mat = raymath::matrix_identity();
+        mat = raymath::matrix_multiply(mat, self.scale_mat);
+        mat = raymath::matrix_multiply(mat, mat_r);
+        mat = raymath::matrix_multiply(mat, mat_y);
+        mat = raymath::matrix_multiply(mat, mat_t);
+
+        let (frame_curr, frame_next, mix) = self.anim_frames();
 
         let dc = render::DrawCall {
             matrix: mat,
@@ -255,14 +517,19 @@ impl Pig {
         };
         render::draw(dc).unwrap();
 
-        render::push_debug_cube_wires(self.base.location.into(), 2., 2., 2., [1., 1., 1., 1.]).unwrap()
-    }
-
-    pub fn get_mesh(&self) -> Vec<[raymath::Vector3; 3]> {
-        panic!("don't fetch entity meshes")
-    }
-
-    pub fn get_matrix(&self) -> raymath::Matrix {
-        panic!("don't fetch entity meshes")
+        if cfg!(debug_assertions) {
+            render::push_debug_cylinder_wires(
+                self.position,
+                raymath::vector3_add(
+                    self.position,
+                    Vector3::new(0., self.controller.chest_height(), 0.),
+                ),
+                self.controller.radius,
+                self.controller.radius,
+                11,
+                [1., 0.5, 0.5, 1.],
+            )
+            .unwrap();
+        }
     }
 }
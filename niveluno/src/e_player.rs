@@ -1,46 +1,46 @@
-use core::{f32, panic};
+use core::f32;
 
 use mcap::Surface;
 use raymath::{
-    matrix_rotate_y, matrix_translate, vector2_add, vector2_distance, vector2_dot_product,
-    vector2_length, vector2_normalize, vector2_scale, vector2_subtract, vector3_add,
-    vector3_distance, vector3_dot_product, vector3_length, vector3_multiply, vector3_negate,
-    vector3_normalize, vector3_scale, vector3_subtract, vector3_transform, BoundingBox,
-    RayCollision, Vector2,
+    matrix_rotate_y, vector3_add, vector3_dot_product, vector3_length, vector3_normalize,
+    vector3_scale, vector3_subtract, vector3_transform,
 };
 use sdl2::rect::Point;
 
-use crate::g_game::TopState;
-use crate::g_instance::{get_decor_instances, Instance};
-use crate::map::{self, Entity};
-use crate::math::{
-    closest_point_to_triangle, get_ray_collision_mesh, mesh_tranform, vec3_face_normal, Vector3,
-};
+use crate::e_barrier::Barrier;
+use crate::e_pig::Pig;
+use crate::g_game::{Event, TopState};
+use crate::g_instance::Instance;
+use crate::math::{ToVec3, ToVector3, Vector3};
+use crate::nuerror::NUError;
 use crate::text::{self, OverlaySurface};
-use crate::{asset, g_game};
+use crate::{asset, audio, g_game};
 use crate::{g_instance, input};
-use crate::{render, time};
+use crate::{render, save, time};
 
 pub struct Player {
-    base: Entity,
     pitch: f32,
     yaw: f32,
     pub position: Vector3,
     hud: Box<text::OverlaySurface>,
     speed: f32,
     acceleration: Vector3,
-    velocity: Vector3,
-    on_ground: bool,
     last_floor: Vector3,
     bid: Option<u32>,
     friction: f32,
-    height: f32,
-    radius: f32,
-    chest_height: f32,
-    snap_up: f32,
-    snap_down: f32,
+    // position is copied in and out around each step
+    controller: mcap::CharacterController,
     opt_ass: Option<OptAssets>,
     coins: usize,
+    health: u32,
+    // seconds of invulnerability left
+    invuln_time: f32,
+    // seconds since dying, None while alive
+    dead_time: Option<f32>,
+    // distance walked since the last footstep
+    stride: f32,
+    // seconds until the next attack
+    attack_cooldown: f32,
 }
 
 pub struct OptAssets {
@@ -48,12 +48,27 @@ pub struct OptAssets {
     encounter_bar_frame: Box<text::OverlaySurface>,
 }
 
-// 4x regular gravity, 4x regular terminal velocity
-const GRAVITY: f32 = -36.0;
-const TERMINAL_VEL: f32 = -216.0;
+const MAX_HEALTH: u32 = 5;
+const INVULN_TIME: f32 = 1.0;
+const RESPAWN_DELAY: f32 = 2.0;
+// landing faster than this hurts, 1 more damage every FALL_DAMAGE_STEP
+// past it, 30 is about a 12.5 unit drop
+const FALL_DAMAGE_SPEED: f32 = 30.0;
+const FALL_DAMAGE_STEP: f32 = 10.0;
+const STRIDE_LENGTH: f32 = 4.0;
+// pigs are 2, so they shove the player further than they're shoved
+const MASS: f32 = 1.0;
+const ATTACK_DAMAGE: u32 = 1;
+const ATTACK_COOLDOWN: f32 = 0.5;
+// chest to chest
+const ATTACK_RANGE: f32 = 4.0;
+// cos of how far off straight ahead a pig can be and still get hit
+const ATTACK_CONE: f32 = 0.7;
+// for menus from before the menu entities' 'map' param
+const FALLBACK_START_MAP: &str = "blank";
 
 impl Player {
-    pub fn new(entt: &Entity) -> Self {
+    pub fn new(position: Vector3, yaw: f32) -> Self {
         // timed surface on spawn
         let mut spawn = text::create_text_overlay_surface(text::TextInput {
             text: "SPAWN".to_string(),
@@ -82,28 +97,21 @@ impl Player {
         let height = chest_height + radius;
 
         Self {
-            base: entt.clone(),
             pitch: 0.,
-            yaw: 0.,
-            position: entt.location.into(),
+            yaw,
+            position,
             speed: 96.,
             acceleration: Vector3 {
                 x: 0.,
                 y: 0.,
                 z: 0.,
             },
-            velocity: Vector3 {
-                x: 0.,
-                y: 0.,
-                z: 0.,
-            },
             last_floor: Vector3 {
                 x: 0.,
                 y: 0.,
                 z: 0.,
             },
             bid: None,
-            on_ground: true,
             friction: 0.3,
             hud: match g_game::get_state().unwrap() {
                 TopState::Menu => text::create_text_overlay_surface(text::TextInput {
@@ -133,11 +141,7 @@ impl Player {
                 })
                 .unwrap(),
             },
-            height: height,
-            radius: radius, 
-            chest_height: chest_height,
-            snap_up: snap_up,
-            snap_down: 0.5,
+            controller: mcap::CharacterController::new(position.to_mcapv3(), height, radius),
             opt_ass: match g_game::get_state().unwrap() {
                 TopState::Menu => None,
                 TopState::Play => {
@@ -173,6 +177,11 @@ impl Player {
                 }
             },
             coins: 0,
+            health: MAX_HEALTH,
+            invuln_time: 0.,
+            dead_time: None,
+            stride: 0.,
+            attack_cooldown: 0.,
         }
     }
 
@@ -181,6 +190,8 @@ impl Player {
 
         match g_game::get_state().unwrap() {
             TopState::Play => {
+                self.push_health_hud();
+
                 if self.opt_ass.is_some() {
                     let eb = &mut self.opt_ass.as_mut().unwrap().encounter_bar;
                     let w = eb.w as f64 * (time::get_run_time().unwrap() % 20.) / 20.;
@@ -195,75 +206,30 @@ impl Player {
         }
     }
 
-    pub fn update(&mut self) {
-        let _ = self.base;
-
-        let keys = input::get_keys().unwrap();
-
-        if g_game::get_state().unwrap() == TopState::Menu && keys[input::Key::Jump as usize] == true
-        {
-            // let nmap = asset::get_file("map/nmap.mp").unwrap().unwrap();
-            let nmap = asset::get_file("map/blank.mp").unwrap().unwrap();
-            let payload = mparse::unmarshal(&nmap).unwrap();
-            let level = map::load(payload).unwrap();
-            g_game::set_state(TopState::Play).unwrap();
-            g_game::stage_level(level.clone()).unwrap();
-            return;
-        } else if g_game::get_state().unwrap() == TopState::Menu {
-            render::set_camera_pos(self.position).unwrap();
-            return;
-        }
-
-        let (mouse_x, mouse_y) = input::get_mouse().unwrap();
-        self.pitch = (self.pitch + mouse_y * 0.00015).clamp(-1.5, 1.5);
-        self.yaw = (self.yaw + mouse_x * 0.00015) % (2. * std::f32::consts::PI);
-
-        render::set_camera_pitch(self.pitch).unwrap();
-        render::set_camera_yaw(self.yaw).unwrap();
-
-        self.update_physics();
-
-        let mut bid = None;
-        let bis = g_instance::get_barrier_instances().unwrap();
-        for ba in bis {
-            let g_instance::Instance::EBarrier(barrier) = ba else {
-                continue;
-            };
-
-            if barrier.position_is_inside(vector3_add(
-                self.position,
-                Vector3::new(0., self.height / 2., 0.),
-            )) {
-                bid = Some(barrier.get_id())
-            }
-        }
-        self.bid = bid;
+    fn push_health_hud(&self) {
+        // nf-md-heart, nf-md-heart_outline
+        let hearts = format!(
+            "{}{}",
+            "\u{f02d1}".repeat(self.health as usize),
+            "\u{f02d5}".repeat((MAX_HEALTH - self.health) as usize)
+        );
 
-        render::set_camera_pos(vector3_add(
-            self.position,
-            Vector3::new(0., self.chest_height, 0.),
-        ))
+        let mut surf = text::create_text_overlay_surface(text::TextInput {
+            text: hearts,
+            mode: text::Mode::Solid {
+                color: text::FontColor {
+                    r: 255,
+                    g: 64,
+                    b: 64,
+                    a: 255,
+                },
+            },
+            font: g_game::get_symb_font().unwrap(),
+        })
         .unwrap();
-
-        self.update_hud();
-    }
-
-    pub fn draw_model(&mut self) {
-        // draw cylinder
-        if cfg!(debug_assertions)
-            && g_game::get_state().unwrap() != g_game::TopState::Menu
-        {
-            render::push_debug_cylinder_wires(
-                vector3_add(self.position, Vector3::new(0., self.chest_height, 0.)),
-                vector3_add(self.position, Vector3::new(0., self.height, 0.)),
-                self.radius / 2.,
-                self.radius / 2.,
-                11,
-                [1., 1., 0., 1.]
-            ).unwrap();
-        }
-
-        self.draw_hud();
+        surf.dst_rect.set_x(render::INTERNAL_W - surf.w as i32 - 8);
+        surf.dst_rect.set_y(8);
+        text::push_surface(&surf).unwrap();
     }
 
     pub fn draw_hud(&mut self) {
@@ -273,7 +239,9 @@ impl Player {
             let mut v_text = text::create_text_overlay_surface(text::TextInput {
                 text: format!(
                     "velocity: {{{:>5.1},{:>5.1},{:>5.1}}}",
-                    self.velocity.x, self.velocity.y, self.velocity.z
+                    self.controller.velocity.x,
+                    self.controller.velocity.y,
+                    self.controller.velocity.z
                 ),
                 mode: text::Mode::Solid {
                     color: text::FontColor {
@@ -290,7 +258,7 @@ impl Player {
             text::push_surface(&v_text).unwrap();
 
             let mut v_text = text::create_text_overlay_surface(text::TextInput {
-                text: format!("on_ground: {:?}", self.on_ground),
+                text: format!("on_ground: {:?}", self.controller.on_ground),
                 mode: text::Mode::Solid {
                     color: text::FontColor {
                         r: 167,
@@ -322,17 +290,15 @@ impl Player {
             text::push_surface(&v_text).unwrap();
 
             // get grid pos
-            let grid_pos = {
-                let fgpos = vector3_scale(self.position, 1. / mcap::GRID_SIZE);
-                (fgpos.x.floor() as u32, fgpos.y.floor() as u32, fgpos.z.floor() as u32 )
-            };
-            let collision_surfaces = g_game::get_surface_grid().unwrap().surfaces_in_cell(grid_pos).unwrap_or_default();
+            let grid = g_game::get_surface_grid().unwrap();
+            let grid_pos = grid.cell_of(self.position.to_mcapv3());
+            let collision_surfaces = grid.surfaces_in_cell(grid_pos).unwrap_or_default();
 
             let mut v_text = text::create_text_overlay_surface(text::TextInput {
                 text: format!("grid[{:>2},{:>2},{:>2}]: {} ",
-                    (self.position.x / mcap::GRID_SIZE) as u32,
-                    (self.position.y / mcap::GRID_SIZE) as u32,
-                    (self.position.z / mcap::GRID_SIZE) as u32,
+                    grid_pos.0,
+                    grid_pos.1,
+                    grid_pos.2,
                     collision_surfaces.len(),
                 ),
                 mode: text::Mode::Solid {
@@ -410,14 +376,6 @@ impl Player {
         }
     }
 
-    pub fn get_mesh(&self) -> Vec<[raymath::Vector3; 3]> {
-        panic!("don't fetch entity meshes")
-    }
-
-    pub fn get_matrix(&self) -> raymath::Matrix {
-        panic!("don't fetch entity meshes")
-    }
-
     pub fn update_physics(&mut self) {
 
         let y_mat = matrix_rotate_y(self.yaw);
@@ -443,154 +401,328 @@ impl Player {
             y_mat,
         );
 
-        let move_dir = vector3_normalize(self.acceleration);
-
-        let speed = 100.;
         let sprint_factor = match sprint {
             true => 1.5,
             false => 1.0,
         };
-        let move_speed = match self.on_ground {
-            true => 1. * sprint_factor * speed,
-            false => 0.8 * sprint_factor * speed,
-        };
-        let friction = match self.on_ground {
-            true => 10.,
-            false => 9.,
-        };
-        let friction_factor = 1.0 - (friction * fd).min(1.0);
-        self.velocity = vector3_multiply(self.velocity, Vector3::new(friction_factor, 1., friction_factor));
-        self.velocity = vector3_add(self.velocity, vector3_scale(vector3_scale(move_dir, move_speed), fd));
+        let wish = vector3_scale(vector3_normalize(self.acceleration), sprint_factor);
+
+        let before = self.position.to_mcapv3();
+        self.controller.position = before;
+        let report = self.controller.step(
+            fd,
+            wish.to_mcapv3(),
+            jump,
+            g_game::get_surface_grid().unwrap(),
+        );
+        self.position = self.controller.position.to_rayv3();
+
+        if let Some(speed) = report.landed_speed.filter(|s| *s > FALL_DAMAGE_SPEED) {
+            self.damage(((speed - FALL_DAMAGE_SPEED) / FALL_DAMAGE_STEP) as u32 + 1);
+        }
+
+        if self.controller.on_ground {
+            let moved = self.controller.position - before;
+            self.stride += moved.x.hypot(moved.z);
+        }
+        if report.landed_speed.is_some() || self.stride > STRIDE_LENGTH {
+            self.stride = 0.;
+            if let Some(floor) = &report.floor {
+                if let Err(e) = play_footstep(floor.triangle().material) {
+                    eprintln!("W: footstep failed: {e}");
+                }
+            }
+        }
 
-        if jump && self.on_ground {
-            self.velocity.y = 15.;
-            self.on_ground = false;
+        if cfg!(debug_assertions) {
+            for t in &report.walls {
+                push_debug_surface(t, [0., 1., 0.]);
+            }
+            match &report.floor {
+                Some(Surface::Floor(t)) => push_debug_surface(t, [1., 0., 0.]),
+                Some(Surface::Slide(t)) => push_debug_surface(t, [0., 0., 1.]),
+                _ => {}
+            }
+            if let Some(t) = &report.ceiling {
+                push_debug_surface(t, [1., 1., 0.]);
+            }
         }
+    }
 
-        let max_move_dist = self.radius / 10.;
-        let desired_move = vector3_scale(self.velocity, fd);
-        let desired_move_len = vector3_length(desired_move);
+    // puts the player back on a spawn point, at rest
+    pub fn respawn_at(&mut self, position: Vector3, yaw: f32) {
+        self.position = position;
+        self.yaw = yaw;
+        self.pitch = 0.;
+        self.acceleration = Vector3::new(0., 0., 0.);
+        self.controller.stop();
+        self.health = MAX_HEALTH;
+        self.invuln_time = 0.;
+        self.dead_time = None;
+    }
 
-        let (move_count, move_dist) = if desired_move_len < max_move_dist {
-            (1, desired_move)
-        } else {
-            let move_count = (vector3_length(desired_move) / max_move_dist) as u32 + 1;
-            let move_dist = vector3_scale(desired_move, 1. / move_count as f32);
-            (move_count, move_dist)
-        };
+    pub fn get_save_state(&self) -> save::PlayerState {
+        save::PlayerState {
+            x: self.position.x,
+            y: self.position.y,
+            z: self.position.z,
+            yaw: self.yaw,
+            pitch: self.pitch,
+            coins: self.coins as u32,
+        }
+    }
 
-        for _ in 0..move_count {
+    pub fn apply_save_state(&mut self, state: &save::PlayerState) {
+        self.respawn_at(Vector3::new(state.x, state.y, state.z), state.yaw);
+        self.pitch = state.pitch;
+        self.coins = state.coins as usize;
+    }
 
-            let fd = fd / (move_count as f32);
+    pub fn get_coin(&mut self) {
+        self.coins += 1;
+    }
 
-            let pos = vector3_add(self.position, move_dist);
-            let mut pos = mcap::Vec3::new(pos.x, pos.y, pos.z);
+    pub fn get_yaw(&self) -> f32 {
+        self.yaw
+    }
 
-            let grid_pos = {
-                let fgpos = pos / mcap::GRID_SIZE;
-                (fgpos.x.floor() as u32, fgpos.y.floor() as u32, fgpos.z.floor() as u32 )
+    pub fn chest(&self) -> Vector3 {
+        vector3_add(
+            self.position,
+            Vector3::new(0., self.controller.chest_height(), 0.),
+        )
+    }
+
+    // hits the closest pig in front within reach, if no wall is in the way
+    fn attack(&mut self) -> Result<(), NUError> {
+        self.attack_cooldown = ATTACK_COOLDOWN;
+
+        let from = self.chest();
+        let forward = vector3_transform(Vector3::new(0., 0., 1.), matrix_rotate_y(self.yaw));
+        let grid = g_game::get_surface_grid()?;
+
+        let mut closest = None;
+        for handle in g_instance::find_instances_of::<Pig>()? {
+            let Some(chest) = g_instance::with_instance_of(handle, |pig: &mut Pig| pig.chest())?
+            else {
+                continue;
             };
+            let d = vector3_subtract(chest, from);
+            let dist = vector3_length(d);
+            let flat = vector3_normalize(Vector3::new(d.x, 0., d.z));
+            if dist > ATTACK_RANGE || vector3_dot_product(flat, forward) < ATTACK_CONE {
+                continue;
+            }
+            if grid
+                .raycast(from.to_mcapv3(), d.to_mcapv3(), dist)
+                .is_some()
+            {
+                continue;
+            }
+            if closest.is_none_or(|(_, c)| dist < c) {
+                closest = Some((handle, dist));
+            }
+        }
 
-            let collision_surfaces = g_game::get_surface_grid().unwrap().surfaces_in_cell(grid_pos).unwrap_or_default();
-
-            // wall push
-            let walls;
-            (pos, _, walls) = mcap::push_out_walls_2(pos, self.chest_height, self.radius, collision_surfaces);
-            for t in walls {
-                if cfg!(debug_assertions) {
-                    let v1 = t.verts[0] + t.normal * 0.1;
-                    let v2 = t.verts[1] + t.normal * 0.1;
-                    let v3 = t.verts[2] + t.normal * 0.1;
-                    let v1 = Vector3::new(v1.x, v1.y, v1.z);
-                    let v2 = Vector3::new(v2.x, v2.y, v2.z);
-                    let v3 = Vector3::new(v3.x, v3.y, v3.z);
-                    render::push_debug_triangle(v1, v2, v3, 0., 1., 0., 0.2).unwrap();
-                }
+        let Some((target, _)) = closest else {
+            return Ok(());
+        };
+        g_game::emit(Event::Damaged {
+            target,
+            amount: ATTACK_DAMAGE,
+            source: g_game::get_player_handle()?,
+        })
+    }
+
+    pub fn damage(&mut self, amount: u32) {
+        if self.dead_time.is_some()
+            || self.invuln_time > 0.
+            || g_game::get_state().unwrap() != TopState::Play
+        {
+            return;
+        }
+
+        self.health = self.health.saturating_sub(amount);
+        self.invuln_time = INVULN_TIME;
+
+        if self.health == 0 {
+            self.die();
+        }
+    }
+
+    fn die(&mut self) {
+        self.dead_time = Some(0.);
+        self.controller.velocity = mcap::Vec3::ZERO;
+        self.acceleration = Vector3::new(0., 0., 0.);
+
+        let mut surf = text::create_text_overlay_surface(text::TextInput {
+            text: "YOU DIED".to_string(),
+            mode: text::Mode::Solid {
+                color: text::FontColor {
+                    r: 196,
+                    g: 32,
+                    b: 32,
+                    a: 255,
+                },
+            },
+            font: g_game::get_text_font_lg().unwrap(),
+        })
+        .unwrap();
+        surf.dst_rect
+            .set_x(render::INTERNAL_W / 2 - surf.w as i32 / 2);
+        surf.dst_rect
+            .set_y(render::INTERNAL_H / 2 - surf.h as i32 / 2);
+
+        let ms = (RESPAWN_DELAY * 1000.) as u32;
+        text::push_timed_surface(text::TimedSurface::new(surf, ms)).unwrap();
+    }
+
+    // back to the last checkpoint, or the level's spawn point
+    fn respawn(&mut self) {
+        match g_game::get_respawn_point() {
+            Ok((position, yaw)) => self.respawn_at(position, yaw),
+            Err(e) => {
+                eprintln!("E: nowhere to respawn: {e}");
+                self.respawn_at(self.position, self.yaw);
             }
+        }
+        self.invuln_time = INVULN_TIME;
+    }
+}
 
-            // snap down only when on ground
-            let snap_down = match self.on_ground {
-                true => self.snap_down,
-                false => 0.
-            };
-            // radius is the range from player's center they start falling at
-            // here, when center is half-radius off a ledge, starts falling
-            match mcap::find_floor_height_hotdog_v4(pos, self.snap_up, snap_down, collision_surfaces, self.radius / 2.) {
-                Some((Surface::Floor(floor), y)) => {
-                    pos.y = y;
-                    self.velocity.y = self.velocity.y.max(0.0);
-                    self.on_ground = true;
-
-                    if cfg!(debug_assertions) {
-                        let v1 = floor.verts[0] + floor.normal * 0.1;
-                        let v2 = floor.verts[1] + floor.normal * 0.1;
-                        let v3 = floor.verts[2] + floor.normal * 0.1;
-                        let v1 = Vector3::new(v1.x, v1.y, v1.z);
-                        let v2 = Vector3::new(v2.x, v2.y, v2.z);
-                        let v3 = Vector3::new(v3.x, v3.y, v3.z);
-                        render::push_debug_triangle(v1, v2, v3, 1., 0., 0., 0.2).unwrap();
-                    }
-                }
-                Some((Surface::Slide(slide), y)) => {
-                    pos.y = y;
-                    let n = slide.normal();
-                    let g = mcap::Vec3::new(0.0, GRAVITY, 0.0);
-                    let g_slide = g - n * g.dot(n);
-
-                    let mut velocity = mcap::Vec3::new(self.velocity.x, self.velocity.y, self.velocity.z);
-
-                    // remove velocity into the slope
-                    let vel_into_slope = n * velocity.dot(n);
-                    velocity -= vel_into_slope;
-
-                    // just feels better with 2x grav
-                    velocity += g_slide * fd;
-
-                    self.velocity = Vector3::new(velocity.x, velocity.y, velocity.z);
-
-                    self.on_ground = false;
-
-                    if cfg!(debug_assertions) {
-                        let v1 = slide.verts[0] + slide.normal * 0.1;
-                        let v2 = slide.verts[1] + slide.normal * 0.1;
-                        let v3 = slide.verts[2] + slide.normal * 0.1;
-                        let v1 = Vector3::new(v1.x, v1.y, v1.z);
-                        let v2 = Vector3::new(v2.x, v2.y, v2.z);
-                        let v3 = Vector3::new(v3.x, v3.y, v3.z);
-                        render::push_debug_triangle(v1, v2, v3, 0., 0., 1., 0.2).unwrap();
-                    }
-                }
-                _ => {
-                    // falling
-                    self.velocity.y = (self.velocity.y + GRAVITY * fd).max(TERMINAL_VEL);
-                    self.on_ground = false;
+impl Instance for Player {
+    fn update(&mut self) {
+        let keys = input::get_keys().unwrap();
+
+        if g_game::get_state().unwrap() == TopState::Menu && keys[input::Key::Jump as usize] == true
+        {
+            let (map, spawn) = g_instance::get_menu_start().unwrap_or_else(|e| {
+                eprintln!("W: {e}, starting on '{FALLBACK_START_MAP}'");
+                (FALLBACK_START_MAP.to_string(), None)
+            });
+            match g_game::load_level(&map) {
+                Ok(level) => {
+                    g_game::set_state(TopState::Play).unwrap();
+                    g_game::stage_level_at(level, spawn).unwrap();
                 }
+                Err(e) => eprintln!("E: failed to start on '{map}': {e}"),
             }
+            return;
+        } else if g_game::get_state().unwrap() == TopState::Menu {
+            render::set_camera_pos(self.position).unwrap();
+            return;
+        }
 
-            // cieling clamp
-            if let Some((Surface::Cieling(ciel), y)) = mcap::find_ciel_height_hotdog_v3(pos, self.chest_height, self.height - self.chest_height, collision_surfaces, self.radius / 2. ) {
-                pos.y = y - self.height;
-                self.velocity.y = self.velocity.y.min(0.0);
-
-                if cfg!(debug_assertions) {
-                    let v1 = ciel.verts[0] + ciel.normal * 0.1;
-                    let v2 = ciel.verts[1] + ciel.normal * 0.1;
-                    let v3 = ciel.verts[2] + ciel.normal * 0.1;
-                    let v1 = Vector3::new(v1.x, v1.y, v1.z);
-                    let v2 = Vector3::new(v2.x, v2.y, v2.z);
-                    let v3 = Vector3::new(v3.x, v3.y, v3.z);
-                    render::push_debug_triangle(v1, v2, v3, 1., 1., 0., 0.2).unwrap();
-                }
+        let fd = time::get_delta_time().unwrap() as f32;
+        self.invuln_time = (self.invuln_time - fd).max(0.);
+
+        if let Some(dead_time) = self.dead_time {
+            let dead_time = dead_time + fd;
+            if dead_time < RESPAWN_DELAY {
+                self.dead_time = Some(dead_time);
+                // slumped on the ground
+                render::set_camera_pos(vector3_add(self.position, Vector3::new(0., 0.3, 0.)))
+                    .unwrap();
+                self.update_hud();
+                return;
             }
+            self.respawn();
+        }
+
+        let (mouse_x, mouse_y) = input::get_mouse().unwrap();
+        self.pitch = (self.pitch + mouse_y * 0.00015).clamp(-1.5, 1.5);
+        self.yaw = (self.yaw + mouse_x * 0.00015) % (2. * std::f32::consts::PI);
 
-            self.position = Vector3::new(pos.x, pos.y, pos.z);
+        render::set_camera_pitch(self.pitch).unwrap();
+        render::set_camera_yaw(self.yaw).unwrap();
 
+        self.attack_cooldown = (self.attack_cooldown - fd).max(0.);
+        if keys[input::Key::Action as usize] && self.attack_cooldown == 0. {
+            if let Err(e) = self.attack() {
+                eprintln!("E: player attack failed: {e}");
+            }
         }
 
+        self.update_physics();
 
+        let mut bid = None;
+        let middle = vector3_add(
+            self.position,
+            Vector3::new(0., self.controller.height / 2., 0.),
+        );
+        for handle in g_instance::find_instances_of::<Barrier>().unwrap() {
+            let inside = g_instance::with_instance_of(handle, |barrier: &mut Barrier| {
+                barrier.position_is_inside(middle).then(|| barrier.get_id())
+            });
+            if let Ok(Some(id)) = inside {
+                bid = Some(id)
+            }
+        }
+        self.bid = bid;
+
+        render::set_camera_pos(self.chest()).unwrap();
+
+        self.update_hud();
     }
 
-    pub fn get_coin(&mut self) {
-        self.coins += 1;
+    fn draw_model(&mut self) {
+        // draw cylinder
+        if cfg!(debug_assertions)
+            && g_game::get_state().unwrap() != g_game::TopState::Menu
+        {
+            render::push_debug_cylinder_wires(
+                vector3_add(
+                    self.position,
+                    Vector3::new(0., self.controller.chest_height(), 0.),
+                ),
+                vector3_add(self.position, Vector3::new(0., self.controller.height, 0.)),
+                self.controller.radius / 2.,
+                self.controller.radius / 2.,
+                11,
+                [1., 1., 0., 1.]
+            ).unwrap();
+        }
+
+        self.draw_hud();
     }
+
+    fn on_event(&mut self, event: &Event) {
+        // only ever delivered to the target
+        if let Event::Damaged { amount, .. } = event {
+            self.damage(*amount);
+        }
+    }
+
+    fn get_collider(&self) -> Option<mcap::Collider> {
+        if self.dead_time.is_some() {
+            return None;
+        }
+        let shape = self.controller.collider_shape();
+        Some(mcap::Collider::solid(shape, MASS))
+    }
+
+    // sideways only, standing on a pig isn't supported
+    fn push(&mut self, offset: Vector3) {
+        self.controller.position = self.position.to_mcapv3();
+        self.controller.shove(
+            Vector3::new(offset.x, 0., offset.z).to_mcapv3(),
+            g_game::get_surface_grid().unwrap(),
+        );
+        self.position = self.controller.position.to_rayv3();
+    }
+}
+
+// "step_{tag}" for the material's sound tag
+fn play_footstep(material: mcap::Material) -> Result<(), NUError> {
+    let Some(id) = material.sound else {
+        return Ok(());
+    };
+    let tag = g_game::get_param(id as usize)?;
+    audio::play_sfx(&format!("step_{tag}"))
+}
+
+// slightly off the surface so it isn't z-fighting
+fn push_debug_surface(t: &mcap::Triangle, color: [f32; 3]) {
+    let [v1, v2, v3] = t.verts.map(|v| (v + t.normal * 0.1).to_rayv3());
+    render::push_debug_triangle(v1, v2, v3, color[0], color[1], color[2], 0.2).unwrap();
 }
@@ -1,22 +1,25 @@
 use core::f32;
 
-use crate::g_instance;
+use crate::g_instance::Instance;
 use crate::map::Entity;
-use crate::text;
+use crate::math::ToVec3;
 use crate::time;
 
-use crate::g_game;
+use crate::g_game::{self, Event, Pickup};
 use crate::render;
 
 use raymath::{self, Vector3};
 
+// touching the player's capsule within this picks it up
+const PICKUP_RADIUS: f32 = 1.5;
+
 #[derive(Debug)]
 pub struct PrototypeCoinA {
     base: Entity,
     scale_mat: raymath::Matrix,
     quat: raymath::Quaternion,
     position: Vector3,
-    pub dead: bool,
+    dead: bool,
 }
 
 impl PrototypeCoinA {
@@ -29,7 +32,10 @@ impl PrototypeCoinA {
             dead: false,
         }
     }
-    pub fn update(&mut self) {
+}
+
+impl Instance for PrototypeCoinA {
+    fn update(&mut self) {
 
         let quat_y = raymath::quaternion_from_axis_angle(Vector3::new(0., 1., 0.), 2. *  time::get_delta_time().unwrap() as f32);
         self.quat = raymath::quaternion_multiply(self.quat, quat_y);
@@ -45,38 +51,32 @@ impl PrototypeCoinA {
             self.position,
             16, 128, 255, 0
         ).unwrap();
+    }
+
+    fn get_collider(&self) -> Option<mcap::Collider> {
+        Some(mcap::Collider::sensor(mcap::ColliderShape::Sphere {
+            center: self.position.to_mcapv3(),
+            radius: PICKUP_RADIUS,
+        }))
+    }
 
-        let player = g_instance::get_player_instance().unwrap();
-
-        if raymath::vector3_distance(player.position, self.position) < 3. {
-            player.get_coin();
-            self.dead = true;
-
-
-            // timed surface on coin-get
-            let mut spawn = text::create_text_overlay_surface(text::TextInput {
-                text: "COIN GET".to_string(),
-                mode: text::Mode::Solid {
-                    color: text::FontColor {
-                        r: 32,
-                        g: 196,
-                        b: 64,
-                        a: 255,
-                    },
-                },
-                font: g_game::get_text_font_lg().unwrap(),
-            })
-            .unwrap();
-
-            spawn.dst_rect.set_x(200);
-            spawn.dst_rect.set_y(200);
-
-            let ts = text::TimedSurface::new(spawn, 1000);
-            text::push_timed_surface(ts).unwrap();
+    fn on_event(&mut self, event: &Event) {
+        let Event::Touched { other, .. } = event else {
+            return;
+        };
+        if self.dead || g_game::get_player_handle().ok().flatten() != Some(*other) {
+            return;
         }
+
+        g_game::emit(Event::PickedUp {
+            item: Pickup::Coin,
+            by: *other,
+        })
+        .unwrap();
+        self.dead = true;
     }
 
-    pub fn draw_model(&mut self) {
+    fn draw_model(&mut self) {
         let ref_ent = g_game::get_ref_entity(self.base.ref_id).unwrap();
 
         let mat_r = raymath::quaternion_to_matrix(self.quat);
@@ -103,11 +103,7 @@ impl PrototypeCoinA {
         render::draw(dc).unwrap();
     }
 
-    pub fn get_mesh(&self) -> Vec<[raymath::Vector3; 3]> {
-        panic!("don't fetch entity meshes")
-    }
-
-    pub fn get_matrix(&self) -> raymath::Matrix {
-        panic!("don't fetch entity meshes")
+    fn is_dead(&self) -> bool {
+        self.dead
     }
 }
@@ -0,0 +1,149 @@
+use rhai::{Dynamic, Map, Scope, AST};
+
+use crate::g_instance::Instance;
+use crate::map::Entity;
+use crate::nuerror::NUError;
+use crate::{g_game, render, script};
+
+// a map entity whose behaviour comes from the rhai script named
+// by its 'script' param, e.g. "script/spinner.rhai" in the pak
+//
+// the script may define init(), update(), draw() and activate(),
+// all called with `this` bound to a map of:
+//   x, y, z   position, starts at the entity's location
+//   yaw       radians, on top of the rotation placed in the map
+//   visible   draw the entity's reference model, if it has one
+//   dead      set true to remove the entity
+//   params    every map param, as strings
+pub struct Script {
+    base: Entity,
+    path: String,
+    ast: AST,
+    scope: Scope<'static>,
+    this: Dynamic,
+    // a script stops being called after its first runtime error
+    failed: bool,
+}
+
+impl Script {
+    pub fn new(entt: &Entity) -> Result<Self, NUError> {
+        let path = entt
+            .get_param("script")?
+            .ok_or_else(|| entt.param_error("script", "is required"))?
+            .to_string();
+
+        let ast = script::load(&path)?;
+
+        let mut params = Map::new();
+        for (k, v) in entt.get_all_params()? {
+            params.insert(k.into(), v.into());
+        }
+
+        let mut this = Map::new();
+        this.insert("x".into(), (entt.location[0] as f64).into());
+        this.insert("y".into(), (entt.location[1] as f64).into());
+        this.insert("z".into(), (entt.location[2] as f64).into());
+        this.insert("yaw".into(), (0.).into());
+        this.insert("visible".into(), entt.has_ref.into());
+        this.insert("dead".into(), false.into());
+        this.insert("params".into(), params.into());
+
+        let mut scope = Scope::new();
+        script::run(&ast, &mut scope)?;
+
+        let mut s = Self {
+            base: entt.clone(),
+            path,
+            ast,
+            scope,
+            this: Dynamic::from_map(this),
+            failed: false,
+        };
+        s.call("init");
+
+        Ok(s)
+    }
+
+    fn call(&mut self, name: &str) {
+        if self.failed || !script::has_fn(&self.ast, name) {
+            return;
+        }
+
+        if let Err(e) = script::call(&self.ast, &mut self.scope, &mut self.this, name) {
+            eprintln!("E: script '{}' {e}, disabling it", self.path);
+            self.failed = true;
+        }
+    }
+
+    // scripts are free to assign ints to float fields
+    fn get_f32(&self, key: &str) -> f32 {
+        let Some(this) = self.this.read_lock::<Map>() else {
+            return 0.;
+        };
+        match this.get(key) {
+            Some(v) => v
+                .as_float()
+                .or_else(|_| v.as_int().map(|i| i as f64))
+                .unwrap_or(0.) as f32,
+            None => 0.,
+        }
+    }
+
+    fn get_bool(&self, key: &str) -> bool {
+        let Some(this) = self.this.read_lock::<Map>() else {
+            return false;
+        };
+        this.get(key)
+            .and_then(|v| v.as_bool().ok())
+            .unwrap_or(false)
+    }
+}
+
+impl Instance for Script {
+    fn update(&mut self) {
+        self.call("update");
+    }
+
+    fn draw_model(&mut self) {
+        self.call("draw");
+
+        if !self.base.has_ref || !self.get_bool("visible") {
+            return;
+        }
+
+        let ref_ent = g_game::get_ref_entity(self.base.ref_id).unwrap();
+
+        let scale = self.base.scale;
+        let mat_s = raymath::matrix_scale(scale[0], scale[1], scale[2]);
+        let mat_r = raymath::matrix_multiply(
+            raymath::quaternion_to_matrix(self.base.rotation.into()),
+            raymath::matrix_rotate_y(self.get_f32("yaw")),
+        );
+        let mat_t =
+            raymath::matrix_translate(self.get_f32("x"), self.get_f32("y"), self.get_f32("z"));
+
+        let mut mat = raymath::matrix_identity();
+        mat = raymath::matrix_multiply(mat, mat_s);
+        mat = raymath::matrix_multiply(mat, mat_r);
+        mat = raymath::matrix_multiply(mat, mat_t);
+
+        let dc = render::DrawCall {
+            matrix: mat,
+            texture: ref_ent.texture_handle as u32,
+            f1: ref_ent.frame_handles[0] as i32,
+            f2: ref_ent.frame_handles[0] as i32,
+            mix: 0.,
+            num_verts: ref_ent.num_verts,
+            glow: None,
+        };
+        render::draw(dc).unwrap();
+    }
+
+    fn is_dead(&self) -> bool {
+        self.get_bool("dead")
+    }
+
+    fn activate(&mut self) {
+        self.call("activate");
+    }
+}
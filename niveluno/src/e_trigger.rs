@@ -0,0 +1,69 @@
+use crate::g_instance::Instance;
+use crate::map::Entity;
+use crate::nuerror::NUError;
+use crate::g_game::{self, Event};
+use crate::g_instance;
+
+// activates everything named by its 'target' param
+// when the player walks into it
+pub struct Trigger {
+    base: Entity,
+    target: Option<String>,
+    once: bool,
+    fired: bool,
+    // None until the first update, like Checkpoint
+    was_inside: Option<bool>,
+}
+
+impl Trigger {
+    pub fn new(entt: &Entity) -> Result<Self, NUError> {
+        let target = entt.get_param("target")?.map(str::to_string);
+        let once = entt.get_bool("once", true)?;
+
+        if target.is_none() {
+            eprintln!("W: trigger has no 'target' param, it will never fire");
+        }
+
+        Ok(Self {
+            base: entt.clone(),
+            target,
+            once,
+            fired: false,
+            was_inside: None,
+        })
+    }
+}
+
+impl Instance for Trigger {
+    fn update(&mut self) {
+        if self.once && self.fired {
+            return;
+        }
+
+        let Some(target) = &self.target else {
+            return;
+        };
+
+        let Ok(player) = g_instance::with_player(|player| player.position) else {
+            return;
+        };
+
+        let inside = self.base.contains_point(player);
+        let entered = inside && self.was_inside == Some(false);
+        self.was_inside = Some(inside);
+
+        if !entered {
+            return;
+        }
+
+        g_game::emit(Event::Triggered {
+            target: target.clone(),
+        })
+        .unwrap();
+        self.fired = true;
+    }
+
+    fn draw_model(&mut self) {
+        self.base.draw_debug_volume([1., 1., 0., 1.]);
+    }
+}
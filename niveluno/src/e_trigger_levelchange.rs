@@ -0,0 +1,62 @@
+use crate::g_instance::Instance;
+use crate::map::Entity;
+use crate::nuerror::NUError;
+use crate::g_game::Event;
+use crate::{g_game, g_instance};
+
+pub struct TriggerLevelChange {
+    base: Entity,
+    map: Option<String>,
+    spawn: Option<String>,
+    fired: bool,
+}
+
+impl TriggerLevelChange {
+    pub fn new(entt: &Entity) -> Result<Self, NUError> {
+        let map = entt.get_param("map")?.map(str::to_string);
+        let spawn = entt.get_param("spawn")?.map(str::to_string);
+
+        if map.is_none() {
+            eprintln!("W: trigger_levelchange has no 'map' param, it will never fire");
+        }
+
+        Ok(Self {
+            base: entt.clone(),
+            map,
+            spawn,
+            fired: false,
+        })
+    }
+}
+
+impl Instance for TriggerLevelChange {
+    fn update(&mut self) {
+        if self.fired {
+            return;
+        }
+
+        let Some(map) = &self.map else {
+            return;
+        };
+
+        let Ok(player) = g_instance::with_player(|player| player.position) else {
+            return;
+        };
+
+        if !self.base.contains_point(player) {
+            return;
+        }
+
+        g_game::emit(Event::LevelChange {
+            map: map.clone(),
+            spawn: self.spawn.clone(),
+        })
+        .unwrap();
+        // even if the map fails to load, don't retry every frame
+        self.fired = true;
+    }
+
+    fn draw_model(&mut self) {
+        self.base.draw_debug_volume([1., 0., 1., 1.]);
+    }
+}
@@ -1,19 +1,39 @@
-use crate::g_instance::{Instance, get_decor_instances};
+use crate::e_player::Player;
+use crate::g_instance::Instance;
 use crate::map::{self, LoadedEnttReference};
-use crate::math::{ToVec3, mesh_tranform, vec3_face_normal};
+use crate::math::{mesh_surfaces, mesh_tranform, ToVector3};
 use crate::nuerror::NUError;
 use crate::text;
-use crate::{asset, g_instance, time};
+use crate::{asset, g_instance, input, save, time};
 
-use mcap::{Surface, SurfaceGrid};
-use raymath::vector3_negate;
+use mcap::{ColliderSet, SurfaceGrid};
+use raymath::{vector3_negate, Vector3};
 use rand::prelude::*;
 
 struct GameGod {
     pub current_level: Option<map::Map>,
     pub next_level: Option<map::Map>,
-    pub entts_inst: Vec<Instance>,
+    pub next_spawn: Option<String>,
+    pub spawn_name: Option<String>,
+    pub entts: Vec<Slot>,
+    // empty slots in entts, reused before growing
+    pub free_slots: Vec<usize>,
+    // never reused, so a stale handle can't match a later occupant
+    pub next_generation: u32,
+    pub player: Option<EntityHandle>,
+    // emitted this tick, handled after the update loop
+    pub events: Vec<Event>,
+    // requested this tick, applied at the end of it, see apply_pending
+    pub pending_spawns: Vec<(usize, Box<dyn Instance>)>,
+    pub pending_despawns: Vec<EntityHandle>,
+    // Entity::index of map entities which have died this level
+    pub removed: Vec<usize>,
+    pub pending_save: Option<save::SaveGame>,
+    // (position, yaw) the player last passed a checkpoint at this level
+    pub checkpoint: Option<(Vector3, f32)>,
     pub surface_grid: Option<SurfaceGrid>,
+    // refilled from get_collider every tick
+    pub colliders: ColliderSet<EntityHandle>,
     pub top_state: TopState,
     pub text_font_lg: Option<text::SizedFontHandle>,
     pub text_font_sm: Option<text::SizedFontHandle>,
@@ -33,6 +53,75 @@ impl GameGod {
 
 static mut GAME_GOD: Option<GameGod> = None;
 
+struct Slot {
+    generation: u32,
+    // None when free, or while the instance is being updated
+    inst: Option<Box<dyn Instance>>,
+    // Entity::index, None if not spawned from the map
+    map_index: Option<usize>,
+    // the map-authored 'name' param, what other entities 'target'
+    name: Option<String>,
+}
+
+// stable reference to a spawned instance, it stops resolving
+// once the instance is removed, even if its slot is reused
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct EntityHandle {
+    slot: usize,
+    generation: u32,
+}
+
+// things that happen during update, queued so entities can
+// react to each other without holding &mut to one another
+#[derive(Clone, Debug)]
+pub enum Event {
+    PickedUp {
+        item: Pickup,
+        by: EntityHandle,
+    },
+    // everything named target gets activated
+    Triggered {
+        target: String,
+    },
+    Damaged {
+        target: EntityHandle,
+        amount: u32,
+        source: Option<EntityHandle>,
+    },
+    // sent as the instance is removed, the handle no longer resolves
+    Died {
+        entity: EntityHandle,
+    },
+    LevelChange {
+        map: String,
+        spawn: Option<String>,
+    },
+    // colliders overlapping after update, sent to each side
+    Touched {
+        target: EntityHandle,
+        other: EntityHandle,
+    },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Pickup {
+    Coin,
+}
+
+impl Event {
+    // delivered only to this instance, otherwise to every instance
+    fn target(&self) -> Option<EntityHandle> {
+        match self {
+            Event::Damaged { target, .. } | Event::Touched { target, .. } => Some(*target),
+            _ => None,
+        }
+    }
+}
+
+// events emitted while handling events are handled the same tick,
+// this bounds a chain of instances reacting to each other
+const MAX_EVENT_ROUNDS: usize = 8;
+
 #[derive(Copy, Clone, PartialEq)]
 pub enum TopState {
     Menu,
@@ -48,8 +137,20 @@ pub fn init() -> Result<(), NUError> {
         GAME_GOD = Some(GameGod {
             current_level: None,
             next_level: None,
-            entts_inst: vec![],
+            next_spawn: None,
+            spawn_name: None,
+            entts: vec![],
+            free_slots: vec![],
+            next_generation: 0,
+            player: None,
+            events: vec![],
+            pending_spawns: vec![],
+            pending_despawns: vec![],
+            removed: vec![],
+            pending_save: None,
+            checkpoint: None,
             surface_grid: None,
+            colliders: ColliderSet::new(),
             top_state: TopState::Menu,
             text_font_lg: None,
             text_font_sm: None,
@@ -71,11 +172,8 @@ pub fn init() -> Result<(), NUError> {
     let nerd_symbols_font = text::push_font(nerd_symbols_bytes)?;
     gg.symb_font = Some(text::create_sized_font(nerd_symbols_font, 24)?);
 
-    let menu = asset::get_file("map/menu.mp")?
-        .ok_or_else(|| NUError::MiscError("menu map not found".to_string()))?;
-    let payload = mparse::unmarshal(&menu).unwrap();
-    let level = map::load(payload)?;
-    stage_level(level.clone())?;
+    let level = load_level("menu")?;
+    stage_level(level)?;
 
     Ok(())
 }
@@ -102,6 +200,13 @@ pub fn get_surface_grid<'a>() -> Result<&'a SurfaceGrid, NUError> {
     Ok(&surf_grid)
 }
 
+pub fn get_surface_grid_mut<'a>() -> Result<&'a mut SurfaceGrid, NUError> {
+    GameGod::get()?
+        .surface_grid
+        .as_mut()
+        .ok_or_else(|| NUError::MiscError("surface grid not built".to_string()))
+}
+
 pub fn get_state() -> Result<TopState, NUError> {
     let gg = GameGod::get()?;
     Ok(gg.top_state)
@@ -113,20 +218,154 @@ pub fn set_state(ts: TopState) -> Result<(), NUError> {
     Ok(())
 }
 
+// loads "map/{name}.mp" from the asset archive
+pub fn load_level(name: &str) -> Result<map::Map, NUError> {
+    let path = format!("map/{name}.mp");
+    let bytes = asset::get_file(&path)?
+        .ok_or_else(|| NUError::MiscError(format!("map '{path}' not found")))?;
+    let payload = mparse::unmarshal(&bytes)
+        .map_err(|e| NUError::MiscError(format!("failed to unmarshal '{path}': {e}")))?;
+    map::load(name, payload)
+}
+
 pub fn stage_level(level: map::Map) -> Result<(), NUError> {
+    stage_level_at(level, None)
+}
+
+// spawn is the name of a spawn point in the staged level,
+// None leaves the choice to the level
+pub fn stage_level_at(level: map::Map, spawn: Option<String>) -> Result<(), NUError> {
     let gg = GameGod::get()?;
     gg.next_level = Some(level);
+    gg.next_spawn = spawn;
+    Ok(())
+}
+
+// safe to call during update, the instance is added at the end of
+// the tick and its handle doesn't resolve until then
+pub fn spawn_entity(inst: Box<dyn Instance>) -> Result<EntityHandle, NUError> {
+    let handle = reserve_slot(None, None)?;
+    GameGod::get()?.pending_spawns.push((handle.slot, inst));
+    Ok(handle)
+}
+
+// safe to call during update, the instance is removed at the end of the tick,
+// instances can also remove themselves by returning true from is_dead
+pub fn despawn_entity(handle: EntityHandle) -> Result<(), NUError> {
+    let gg = GameGod::get()?;
+    if !gg.pending_despawns.contains(&handle) {
+        gg.pending_despawns.push(handle);
+    }
     Ok(())
 }
 
-pub fn spawn_entity(inst: Instance) -> Result<(), NUError> {
+// immediate, only for use outside of the update loop
+fn spawn_slot(
+    inst: Box<dyn Instance>,
+    map_index: Option<usize>,
+    name: Option<String>,
+) -> Result<EntityHandle, NUError> {
+    let handle = reserve_slot(map_index, name)?;
+    GameGod::get()?.entts[handle.slot].inst = Some(inst);
+    Ok(handle)
+}
+
+fn reserve_slot(map_index: Option<usize>, name: Option<String>) -> Result<EntityHandle, NUError> {
+    let gg = GameGod::get()?;
+
+    let generation = gg.next_generation;
+    gg.next_generation += 1;
+
+    let slot = Slot {
+        generation,
+        inst: None,
+        map_index,
+        name,
+    };
+
+    let slot_id = match gg.free_slots.pop() {
+        Some(i) => {
+            gg.entts[i] = slot;
+            i
+        }
+        None => {
+            gg.entts.push(slot);
+            gg.entts.len() - 1
+        }
+    };
+
+    Ok(EntityHandle {
+        slot: slot_id,
+        generation,
+    })
+}
+
+fn free_slot(slot_id: usize) -> Result<(), NUError> {
     let gg = GameGod::get()?;
 
-    gg.entts_inst.push(inst);
+    let slot = &mut gg.entts[slot_id];
+    slot.inst = None;
+    slot.name = None;
+    if let Some(index) = slot.map_index.take() {
+        gg.removed.push(index);
+    }
+    gg.free_slots.push(slot_id);
 
     Ok(())
 }
 
+// f gets the instance taken out of its slot, like run's update loop,
+// so nothing f looks up can hand back a second &mut to it,
+// None if the instance has been removed or is already out
+pub fn with_instance<R>(
+    handle: EntityHandle,
+    f: impl FnOnce(&mut dyn Instance) -> R,
+) -> Result<Option<R>, NUError> {
+    let gg = GameGod::get()?;
+
+    let Some(slot) = gg.entts.get_mut(handle.slot) else {
+        return Ok(None);
+    };
+    if slot.generation != handle.generation {
+        return Ok(None);
+    }
+    let Some(mut inst) = slot.inst.take() else {
+        return Ok(None);
+    };
+
+    let out = f(inst.as_mut());
+
+    // f may have changed level, the slot isn't this instance's any more
+    if let Some(slot) = gg.entts.get_mut(handle.slot) {
+        if slot.generation == handle.generation {
+            slot.inst = Some(inst);
+        }
+    }
+
+    Ok(Some(out))
+}
+
+// every live instance whose 'name' param matches
+pub fn find_by_name(name: &str) -> Result<Vec<EntityHandle>, NUError> {
+    let gg = GameGod::get()?;
+
+    Ok(gg
+        .entts
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.name.as_deref() == Some(name))
+        .map(|(i, s)| EntityHandle {
+            slot: i,
+            generation: s.generation,
+        })
+        .collect())
+}
+
+pub fn get_player_handle() -> Result<Option<EntityHandle>, NUError> {
+    let gg = GameGod::get()?;
+    Ok(gg.player)
+}
+
 pub fn get_map_ref_ents<'a>() -> Result<&'a Vec<LoadedEnttReference>, NUError> {
     let gg = GameGod::get()?;
     let curr = gg.current_level.as_ref().unwrap();
@@ -151,40 +390,94 @@ pub fn init_level(level: &map::Map) -> Result<(), NUError> {
     //     }
     // }
 
-    gg.entts_inst = vec![];
+    gg.entts = vec![];
+    gg.free_slots = vec![];
+    gg.player = None;
+    // all of these refer to the previous level
+    gg.events = vec![];
+    gg.pending_spawns = vec![];
+    gg.pending_despawns = vec![];
+    gg.checkpoint = None;
     for me in &level.map_entities {
-        let entt_inst =
-            g_instance::instance_from_str(level.payload.ern_data[me.ref_id].as_str(), me);
-        if entt_inst.is_some() {
-            spawn_entity(entt_inst.unwrap())?;
+        // already dead in a loaded save
+        if gg.removed.contains(&me.index) {
+            continue;
+        }
+        let reference = match me.has_ref {
+            true => level.ref_entities.get(me.ref_id),
+            false => None,
+        };
+        let entt_inst = g_instance::instance_from_str(
+            level.payload.ern_data[me.ref_id].as_str(),
+            me,
+            reference,
+        );
+        if let Some(inst) = entt_inst {
+            // already validated by the constructor if it's used
+            let name = me.get_param("name").ok().flatten().map(str::to_string);
+            spawn_slot(inst, Some(me.index), name)?;
         }
     }
 
-    let decs = get_decor_instances().unwrap();
+    // the player isn't placed in maps, it's created on a spawn point
+    let spawn_name = gg.spawn_name.clone();
+    spawn_player(spawn_name.as_deref())?;
+
+    if let Some(save) = gg.pending_save.take() {
+        g_instance::with_player(|player| player.apply_save_state(&save.player))?;
+        gg.checkpoint = save
+            .checkpoint
+            .map(|c| (Vector3::new(c.x, c.y, c.z), c.yaw));
+    }
 
     let rt = time::get_fresh_run_time_ms().unwrap();
 
-    let mut all_surfs = vec![];
-    for dec in decs {
-        let mesh = dec.get_mesh();
-        let mat = dec.get_matrix();
-        let mesh = mesh_tranform(mesh, mat);
-
-        mesh.iter().for_each(|tri| {
-                let s = Surface::new(
-                    [
-                        tri[0].to_mcapv3(),
-                        tri[1].to_mcapv3(),
-                        tri[2].to_mcapv3(),
-                    ],
-                    vec3_face_normal(tri[0], tri[1], tri[2]).to_mcapv3(),
-                );
-                all_surfs.push(s);
-            });
+    // static decor, by the map entity it came from
+    let mut decor = vec![];
+    for slot in &gg.entts {
+        let Some(dec) = slot.inst.as_deref().filter(|i| i.is_decor()) else {
+            continue;
+        };
+        let (Some(mesh), Some(mat)) = (dec.get_mesh(), dec.get_matrix()) else {
+            continue;
+        };
+        if !mesh.is_empty() {
+            decor.push((slot.map_index, mesh, mat, dec.get_materials()));
+        }
+    }
+
+    // the map's baked grid only holds if the same decor collides
+    let indices: Vec<Option<usize>> = decor.iter().map(|d| d.0).collect();
+    let baked = level.collision.as_ref().and_then(|c| {
+        let same = indices
+            .iter()
+            .copied()
+            .eq(c.instances.iter().copied().map(Some));
+        match same {
+            true => {
+                SurfaceGrid::from_cell_indices(c.surfaces.clone(), c.cells.clone(), level.grid_size)
+            }
+            false => None,
+        }
+    });
+    if baked.is_none() && level.collision.is_some() {
+        eprintln!("W: baked collision doesn't match the level's decor, rebuilding");
     }
-    gg.surface_grid = Some(SurfaceGrid::new(all_surfs));
 
-    eprintln!("surface_grid_time: {}ms", time::get_fresh_run_time_ms().unwrap() - rt);
+    let is_baked = baked.is_some();
+    gg.surface_grid = Some(baked.unwrap_or_else(|| {
+        let mut all_surfs = vec![];
+        for (_, mesh, mat, materials) in decor {
+            let mesh = mesh_tranform(mesh, mat);
+            all_surfs.extend(mesh_surfaces(&mesh, &materials.unwrap_or_default()));
+        }
+        SurfaceGrid::with_cell_size(all_surfs, level.grid_size)
+    }));
+
+    eprintln!(
+        "surface_grid_time: {}ms, baked: {is_baked}",
+        time::get_fresh_run_time_ms().unwrap() - rt
+    );
 
     // ensure that delta time isn't huge because of load time
     // (it'll cause things like player movement to have one insane
@@ -194,36 +487,291 @@ pub fn init_level(level: &map::Map) -> Result<(), NUError> {
     Ok(())
 }
 
+// finds the spawn point matching name,
+// falling back to the level's first spawn point
+fn find_spawn(name: Option<&str>) -> Result<(Vector3, f32), NUError> {
+    match g_instance::get_spawn_point(name) {
+        Err(e) if name.is_some() => {
+            eprintln!("W: {e}, using first spawn point");
+            g_instance::get_spawn_point(None)
+        }
+        result => result,
+    }
+}
+
+fn spawn_player(name: Option<&str>) -> Result<(), NUError> {
+    let (position, yaw) = match find_spawn(name) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("W: level has no player, {e}");
+            return Ok(());
+        }
+    };
+
+    let handle = spawn_slot(Box::new(Player::new(position, yaw)), None, None)?;
+    GameGod::get()?.player = Some(handle);
+
+    Ok(())
+}
+
+pub fn set_checkpoint(position: Vector3, yaw: f32) -> Result<(), NUError> {
+    GameGod::get()?.checkpoint = Some((position, yaw));
+    Ok(())
+}
+
+// the last checkpoint passed, otherwise the spawn point the level was entered at
+pub fn get_respawn_point() -> Result<(Vector3, f32), NUError> {
+    let gg = GameGod::get()?;
+
+    match gg.checkpoint {
+        Some(c) => Ok(c),
+        None => find_spawn(gg.spawn_name.as_deref()),
+    }
+}
+
 pub fn run() -> Result<(), NUError> {
     let gg = GameGod::get()?;
 
+    let pressed = input::get_pressed_keys()?;
+    if pressed[input::Key::QuickSave as usize] && gg.top_state == TopState::Play {
+        match save_game() {
+            Ok(_) => push_notice("GAME SAVED")?,
+            Err(e) => eprintln!("E: quicksave failed: {e}"),
+        }
+    }
+    if pressed[input::Key::QuickLoad as usize] {
+        if let Err(e) = load_game() {
+            eprintln!("E: quickload failed: {e}");
+        }
+    }
+
     // only swap in/out entities outside of the update loop
     if gg.next_level.is_some() {
         gg.current_level = gg.next_level.take();
+        gg.spawn_name = gg.next_spawn.take();
+        gg.removed = match &gg.pending_save {
+            Some(save) => save.removed.iter().map(|i| *i as usize).collect(),
+            None => vec![],
+        };
         init_level(gg.current_level.as_ref().unwrap())?;
     }
 
-    let mut dead = vec![];
-    for (i, entt) in gg.entts_inst.iter_mut().enumerate() {
+    // the instance is taken out of its slot while it updates, so
+    // lookups it makes can't hand back a second &mut to itself
+    for i in 0..gg.entts.len() {
+        let Some(mut entt) = gg.entts[i].inst.take() else {
+            continue;
+        };
         entt.update();
         if entt.is_dead() {
-            dead.push(i);
+            despawn_entity(EntityHandle {
+                slot: i,
+                generation: gg.entts[i].generation,
+            })?;
         }
+        gg.entts[i].inst = Some(entt);
     }
 
-    dead.sort();
-    dead.reverse();
-    for i in dead {
-        gg.entts_inst.remove(i);
-    }
+    apply_pending()?;
+    resolve_collisions()?;
+    dispatch_events()?;
+    // anything event handlers spawned or despawned,
+    // Died events from these are dispatched next tick
+    apply_pending()?;
 
-    for entt in &mut gg.entts_inst {
+    for entt in gg.entts.iter_mut().filter_map(|s| s.inst.as_deref_mut()) {
         entt.draw_model();
     }
 
     Ok(())
 }
 
+fn apply_pending() -> Result<(), NUError> {
+    let gg = GameGod::get()?;
+
+    for (slot_id, inst) in std::mem::take(&mut gg.pending_spawns) {
+        gg.entts[slot_id].inst = Some(inst);
+    }
+
+    for handle in std::mem::take(&mut gg.pending_despawns) {
+        let slot = &gg.entts[handle.slot];
+        // already removed
+        if slot.generation != handle.generation || slot.inst.is_none() {
+            continue;
+        }
+
+        emit(Event::Died { entity: handle })?;
+        free_slot(handle.slot)?;
+    }
+
+    Ok(())
+}
+
+// solids overlapping after everything has moved are pushed apart,
+// sensors only see Touched
+fn resolve_collisions() -> Result<(), NUError> {
+    let gg = GameGod::get()?;
+
+    gg.colliders.clear();
+    for (i, slot) in gg.entts.iter().enumerate() {
+        let Some(collider) = slot.inst.as_ref().and_then(|inst| inst.get_collider()) else {
+            continue;
+        };
+        let handle = EntityHandle {
+            slot: i,
+            generation: slot.generation,
+        };
+        gg.colliders.add(handle, collider);
+    }
+
+    for contact in gg.colliders.contacts() {
+        emit(Event::Touched {
+            target: contact.a,
+            other: contact.b,
+        })?;
+        emit(Event::Touched {
+            target: contact.b,
+            other: contact.a,
+        })?;
+    }
+
+    for (handle, offset) in gg.colliders.separation() {
+        if let Some(inst) = gg.entts[handle.slot].inst.as_deref_mut() {
+            inst.push(offset.to_rayv3());
+        }
+    }
+
+    Ok(())
+}
+
+pub fn emit(event: Event) -> Result<(), NUError> {
+    let gg = GameGod::get()?;
+    gg.events.push(event);
+    Ok(())
+}
+
+fn dispatch_events() -> Result<(), NUError> {
+    let gg = GameGod::get()?;
+
+    for _ in 0..MAX_EVENT_ROUNDS {
+        if gg.events.is_empty() {
+            return Ok(());
+        }
+
+        for event in std::mem::take(&mut gg.events) {
+            apply_rules(&event)?;
+
+            match event.target() {
+                Some(handle) => {
+                    if gg.entts.get(handle.slot).map(|s| s.generation) == Some(handle.generation) {
+                        deliver_event(handle.slot, &event);
+                    }
+                }
+                None => {
+                    for i in 0..gg.entts.len() {
+                        deliver_event(i, &event);
+                    }
+                }
+            }
+        }
+    }
+
+    eprintln!(
+        "W: dropping {} events still queued after {MAX_EVENT_ROUNDS} rounds",
+        gg.events.len()
+    );
+    gg.events.clear();
+
+    Ok(())
+}
+
+// taken out of its slot like in run's update loop
+fn deliver_event(slot_id: usize, event: &Event) {
+    let gg = GameGod::get().unwrap();
+
+    let Some(mut inst) = gg.entts[slot_id].inst.take() else {
+        return;
+    };
+    inst.on_event(event);
+    gg.entts[slot_id].inst = Some(inst);
+}
+
+// game rules, applied before any instance sees the event
+fn apply_rules(event: &Event) -> Result<(), NUError> {
+    match event {
+        Event::PickedUp {
+            item: Pickup::Coin,
+            by,
+        } => {
+            // only the player counts coins
+            let _ = g_instance::with_instance_of(*by, |player: &mut Player| player.get_coin());
+            push_notice("COIN GET")?;
+        }
+        Event::Triggered { target } => g_instance::activate_targets(target)?,
+        Event::LevelChange { map, spawn } => match load_level(map) {
+            Ok(level) => stage_level_at(level, spawn.clone())?,
+            Err(e) => eprintln!("E: failed to change level to '{map}': {e}"),
+        },
+        Event::Damaged { .. } | Event::Died { .. } | Event::Touched { .. } => {}
+    }
+
+    Ok(())
+}
+
+pub fn save_game() -> Result<(), NUError> {
+    let gg = GameGod::get()?;
+
+    let level = gg
+        .current_level
+        .as_ref()
+        .ok_or(NUError::MiscError("level not set".to_string()))?;
+    let player = g_instance::with_player(|player| player.get_save_state())?;
+
+    save::write(&save::SaveGame {
+        level: level.name.clone(),
+        player,
+        removed: gg.removed.iter().map(|i| *i as u32).collect(),
+        checkpoint: gg.checkpoint.map(|(p, yaw)| save::CheckpointState {
+            x: p.x,
+            y: p.y,
+            z: p.z,
+            yaw,
+        }),
+    })
+}
+
+// the save is applied once its level has been initialized
+pub fn load_game() -> Result<(), NUError> {
+    let gg = GameGod::get()?;
+
+    let save = save::read()?;
+    let level = load_level(&save.level)?;
+
+    gg.pending_save = Some(save);
+    gg.top_state = TopState::Play;
+    stage_level(level)
+}
+
+pub fn push_notice(notice: &str) -> Result<(), NUError> {
+    let mut surf = text::create_text_overlay_surface(text::TextInput {
+        text: notice.to_string(),
+        mode: text::Mode::Solid {
+            color: text::FontColor {
+                r: 32,
+                g: 196,
+                b: 64,
+                a: 255,
+            },
+        },
+        font: get_text_font_lg()?,
+    })?;
+
+    surf.dst_rect.set_x(200);
+    surf.dst_rect.set_y(200);
+
+    text::push_timed_surface(text::TimedSurface::new(surf, 1000))
+}
+
 pub fn get_param<'a>(id: usize) -> Result<&'a str, NUError> {
     let gg = GameGod::get()?;
 
@@ -279,25 +827,24 @@ pub fn get_animation_ids(animations: &[&[&str]], ref_ent: &LoadedEnttReference)
     animation_ids
 }
 
-pub fn get_filtered_instances<'a, F>(filter_fn: F) -> Result<Vec<&'a mut Instance>, NUError>
+// every live instance filter accepts, see with_instance
+pub fn find_instances<F>(filter_fn: F) -> Result<Vec<EntityHandle>, NUError>
 where
-    F: Fn(&mut Instance) -> bool,
+    F: Fn(&dyn Instance) -> bool,
 {
     let gg = GameGod::get()?;
-    let all = &mut gg.entts_inst;
-
-    let mut filtered: Vec<&'a mut Instance> = Vec::new();
-
-    for instance in all.iter_mut() {
-        if filter_fn(instance) {
-            let instance_ptr = instance as *mut Instance;
-            unsafe {
-                filtered.push(&mut *instance_ptr);
-            }
-        }
-    }
 
-    Ok(filtered)
+    // skips the instance currently updating, see run
+    Ok(gg
+        .entts
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.inst.as_deref().is_some_and(&filter_fn))
+        .map(|(i, s)| EntityHandle {
+            slot: i,
+            generation: s.generation,
+        })
+        .collect())
 }
 
 pub fn get_rng<'a>() -> Result<&'a mut StdRng, NUError> {
@@ -17,6 +17,8 @@ pub enum Key {
     Action,
     Jump,
     Sprint,
+    QuickSave,
+    QuickLoad,
 }
 
 struct InputGod {
@@ -25,7 +27,9 @@ struct InputGod {
     pub mouse_speed: f32,
     pub mouse_invert: bool,
     pub quit: bool,
-    pub keys: [bool; 9],
+    pub keys: [bool; 11],
+    // keys which went down this frame
+    pub pressed: [bool; 11],
     pub fullscreen: bool,
 }
 
@@ -52,7 +56,8 @@ pub fn init() -> Result<(), NUError> {
         mouse_speed: 10., // 0-50 // todo, verify
         mouse_invert: false,
         quit: false,
-        keys: [false; 9],
+        keys: [false; 11],
+        pressed: [false; 11],
         fullscreen: false,
     };
 
@@ -68,6 +73,7 @@ pub fn consume(window: &mut Window, mouse: &MouseUtil, event_pump: &mut EventPum
 
     ig.mouse_x = 0.;
     ig.mouse_y = 0.;
+    ig.pressed = [false; 11];
 
     // something's up with later versions of sdl2 here, it panics on values from the reserved
     // range, so now we're going to check scancodes individually
@@ -83,6 +89,7 @@ pub fn consume(window: &mut Window, mouse: &MouseUtil, event_pump: &mut EventPum
                 repeat: false,
                 ..
             } => {
+                let held = ig.keys;
                 match key {
                     Keycode::Up    | Keycode::W => { ig.keys[Key::Up     as usize] = true; },
                     Keycode::Left  | Keycode::A => { ig.keys[Key::Left   as usize] = true; },
@@ -92,8 +99,13 @@ pub fn consume(window: &mut Window, mouse: &MouseUtil, event_pump: &mut EventPum
                                      Keycode::E => { ig.keys[Key::Next   as usize] = true; },
                     Keycode::Space              => { ig.keys[Key::Jump   as usize] = true; },
                     Keycode::LShift             => { ig.keys[Key::Sprint as usize] = true; }
+                    Keycode::F5                 => { ig.keys[Key::QuickSave as usize] = true; }
+                    Keycode::F9                 => { ig.keys[Key::QuickLoad as usize] = true; }
                     _ => {}
                 }
+                for i in 0..ig.keys.len() {
+                    ig.pressed[i] |= ig.keys[i] && !held[i];
+                }
             },
             Event::KeyUp {
                 keycode: Some(key),
@@ -109,6 +121,8 @@ pub fn consume(window: &mut Window, mouse: &MouseUtil, event_pump: &mut EventPum
                                      Keycode::E => { ig.keys[Key::Next   as usize] = false; },
                     Keycode::Space              => { ig.keys[Key::Jump   as usize] = false; },
                     Keycode::LShift             => { ig.keys[Key::Sprint as usize] = false; }
+                    Keycode::F5                 => { ig.keys[Key::QuickSave as usize] = false; }
+                    Keycode::F9                 => { ig.keys[Key::QuickLoad as usize] = false; }
                     Keycode::Return if alt_pressed => {
                         let mut fs = FullscreenType::Desktop;
                         if ig.fullscreen {
@@ -180,6 +194,10 @@ pub fn get_mouse() -> Result<(f32, f32), NUError> {
     Ok((ig.mouse_x, ig.mouse_y))
 }
 
-pub fn get_keys() -> Result<[bool; 9], NUError> {
+pub fn get_keys() -> Result<[bool; 11], NUError> {
     Ok(InputGod::get()?.keys)
 }
+
+pub fn get_pressed_keys() -> Result<[bool; 11], NUError> {
+    Ok(InputGod::get()?.pressed)
+}
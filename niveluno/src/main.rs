@@ -9,6 +9,8 @@ mod map;
 mod math;
 mod nuerror;
 mod render;
+mod save;
+mod script;
 mod text;
 mod time;
 
@@ -17,14 +19,20 @@ mod g_game;
 mod g_instance;
 // pak/entity??
 mod e_barrier;
+mod e_checkpoint;
 mod e_coplight;
 mod e_copfire;
 mod e_gcyl;
+mod e_hazard;
+mod e_info_spawn;
 mod e_light;
 mod e_menu;
 mod e_pig;
 mod e_player;
 mod e_prototype_coin_a;
+mod e_script;
+mod e_trigger;
+mod e_trigger_levelchange;
 // pak/decor??
 mod d_generic;
 mod d_floor;
@@ -99,6 +107,7 @@ fn init_nu() -> Result<(), nuerror::NUError> {
     audio::init()?;
     input::init()?;
     asset::init()?;
+    script::init()?;
 
     // always last
     g_game::init()?;
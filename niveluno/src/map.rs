@@ -1,7 +1,8 @@
-use mparse::exports::Payload;
+use mparse::exports::{Collision, Payload, SurfaceMaterial};
 
 use crate::{
-    math::{self, Vector3},
+    g_game,
+    math::{self, ToVec3, Vector3},
     nuerror::NUError,
     render::{self, create_texture, PngBin},
 };
@@ -15,13 +16,28 @@ pub struct MapPayload {
 
 #[derive(Clone, Debug)]
 pub struct Map {
+    pub name: String,
     pub payload: MapPayload,
     pub map_entities: Vec<Entity>,
     pub ref_entities: Vec<LoadedEnttReference>,
+    // collision cell size, picked per map in nmcc
+    pub grid_size: f32,
+    pub collision: Option<BakedCollision>,
+}
+
+// static decor collision as bucketed by nmcc, see g_game::init_level
+#[derive(Clone, Debug)]
+pub struct BakedCollision {
+    // Entity::index of every decor in surfaces, in map order
+    pub instances: Vec<usize>,
+    pub surfaces: Vec<mcap::Surface>,
+    pub cells: Vec<(mcap::Cell, Vec<u32>)>,
 }
 
 #[derive(Clone, Debug)]
 pub struct Entity {
+    // position in Map::map_entities, stable for a given map file
+    pub index: usize,
     pub ref_id: usize,
     pub has_ref: bool,
     pub params: Vec<u32>, // indexes to [k,v,k,v,k,v] etc
@@ -38,7 +54,12 @@ pub struct LoadedEnttReference {
     pub frame_names: Vec<String>,
     pub frame_handles: Vec<usize>,
     pub mesh: Vec<[Vector3; 3]>,
+    // one per mesh triangle
+    pub materials: Vec<mcap::Material>,
     pub num_verts: usize,
+    pub is_decor: bool,
+    pub no_collide: bool,
+    pub no_draw: bool,
 }
 
 fn pack_floats(verts: Vec<Vec<[f32; 3]>>, uvs: Vec<[f32; 2]>) -> Result<Vec<usize>, NUError> {
@@ -91,7 +112,236 @@ fn pack_floats(verts: Vec<Vec<[f32; 3]>>, uvs: Vec<[f32; 2]>) -> Result<Vec<usiz
     Ok(frame_handles)
 }
 
-pub fn load(payload: Payload) -> Result<Map, NUError> {
+impl Entity {
+    // location, rotation and scale as a shape, see contains_point
+    pub fn volume(&self) -> mcap::Shape {
+        let rotation = mcap::Quat::from_array(self.rotation);
+        let half = mcap::Vec3::from_array(self.scale) / 2.;
+        let location = mcap::Vec3::from_array(self.location);
+        mcap::Shape::Box {
+            center: location + rotation * mcap::Vec3::new(0., half.y, 0.),
+            rotation,
+            half,
+        }
+    }
+
+    // the entity's scale as an upright box, centered on x/z,
+    // and extending upwards from the entity's location
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        self.volume().contains_point(point.to_mcapv3())
+    }
+
+    // the volume as wires, debug builds only
+    pub fn draw_debug_volume(&self, color: [f32; 4]) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+        let center = Vector3::new(
+            self.location[0],
+            self.location[1] + self.scale[1] / 2.,
+            self.location[2],
+        );
+        render::push_debug_cube_wires(center, self.scale[0], self.scale[1], self.scale[2], color)
+            .unwrap();
+    }
+
+    // the reference name, e.g. "light", for messages
+    pub fn name(&self) -> String {
+        match g_game::get_map_ern_data() {
+            Ok(ern) => ern[self.ref_id].clone(),
+            Err(_) => "<unknown>".to_string(),
+        }
+    }
+
+    // raw value of a map-authored param, None if it isn't set
+    pub fn get_param(&self, key: &str) -> Result<Option<&str>, NUError> {
+        for kv in self.params.chunks(2) {
+            if kv.len() != 2 {
+                return Err(self.param_error(key, "has a key with no value"));
+            }
+            if g_game::get_param(kv[0] as usize)? == key {
+                return Ok(Some(g_game::get_param(kv[1] as usize)?));
+            }
+        }
+
+        Ok(None)
+    }
+
+    // every key/value pair, in map order
+    pub fn get_all_params(&self) -> Result<Vec<(String, String)>, NUError> {
+        let mut out = vec![];
+        for kv in self.params.chunks(2) {
+            if kv.len() != 2 {
+                let key = g_game::get_param(kv[0] as usize)?;
+                return Err(self.param_error(key, "has a key with no value"));
+            }
+            out.push((
+                g_game::get_param(kv[0] as usize)?.to_string(),
+                g_game::get_param(kv[1] as usize)?.to_string(),
+            ));
+        }
+
+        Ok(out)
+    }
+
+    pub fn get_string(&self, key: &str, default: &str) -> Result<String, NUError> {
+        Ok(self.get_param(key)?.unwrap_or(default).to_string())
+    }
+
+    pub fn get_int(&self, key: &str, default: i32) -> Result<i32, NUError> {
+        self.get_parsed(key, default, "an int")
+    }
+
+    pub fn get_float(&self, key: &str, default: f32) -> Result<f32, NUError> {
+        self.get_parsed(key, default, "a float")
+    }
+
+    // "true"/"false" or "1"/"0"
+    pub fn get_bool(&self, key: &str, default: bool) -> Result<bool, NUError> {
+        match self.get_param(key)? {
+            None => Ok(default),
+            Some("true") | Some("1") => Ok(true),
+            Some("false") | Some("0") => Ok(false),
+            Some(v) => Err(self.param_error(key, &format!("'{v}' is not a bool"))),
+        }
+    }
+
+    // "x,y,z"
+    pub fn get_vec3(&self, key: &str, default: Vector3) -> Result<Vector3, NUError> {
+        let Some(value) = self.get_param(key)? else {
+            return Ok(default);
+        };
+
+        parse_vec3(value)
+            .ok_or_else(|| self.param_error(key, &format!("'{value}' is not a vec3 'x,y,z'")))
+    }
+
+    // "x,y,z;x,y,z;...", empty if not set
+    pub fn get_vec3_list(&self, key: &str) -> Result<Vec<Vector3>, NUError> {
+        let Some(value) = self.get_param(key)? else {
+            return Ok(vec![]);
+        };
+
+        value
+            .split(';')
+            .map(|v| {
+                parse_vec3(v)
+                    .ok_or_else(|| self.param_error(key, &format!("'{v}' is not a vec3 'x,y,z'")))
+            })
+            .collect()
+    }
+
+    // "r,g,b" or "r,g,b,a", 0-255, alpha defaults to 255
+    pub fn get_color(&self, key: &str, default: [u8; 4]) -> Result<[u8; 4], NUError> {
+        let Some(value) = self.get_param(key)? else {
+            return Ok(default);
+        };
+
+        let parts: Vec<Option<u8>> = value.split(',').map(|p| p.trim().parse().ok()).collect();
+        match parts[..] {
+            [Some(r), Some(g), Some(b)] => Ok([r, g, b, 255]),
+            [Some(r), Some(g), Some(b), Some(a)] => Ok([r, g, b, a]),
+            _ => Err(self.param_error(key, &format!("'{value}' is not a color 'r,g,b[,a]'"))),
+        }
+    }
+
+    fn get_parsed<T: std::str::FromStr>(
+        &self,
+        key: &str,
+        default: T,
+        what: &str,
+    ) -> Result<T, NUError> {
+        match self.get_param(key)? {
+            None => Ok(default),
+            Some(v) => v
+                .trim()
+                .parse()
+                .map_err(|_| self.param_error(key, &format!("'{v}' is not {what}"))),
+        }
+    }
+
+    // for range checks etc. done by the caller
+    pub fn param_error(&self, key: &str, msg: &str) -> NUError {
+        NUError::MiscError(format!(
+            "{} (map entity {}) param '{key}': {msg}",
+            self.name(),
+            self.index
+        ))
+    }
+}
+
+// sound stays a kvs id, see g_game::get_param
+fn surface_material(m: &SurfaceMaterial) -> mcap::Material {
+    mcap::Material {
+        friction: m.friction,
+        slide_angle: (m.slide_angle > 0.).then(|| m.slide_angle.to_radians()),
+        ice: m.ice,
+        mud: m.mud,
+        bouncy: m.bouncy,
+        sound: Some(m.sound),
+    }
+}
+
+fn valid_grid_size(size: f32) -> bool {
+    size.is_finite() && size >= mcap::MIN_GRID_SIZE
+}
+
+fn load_collision(c: &Collision, materials: &[SurfaceMaterial]) -> Result<BakedCollision, NUError> {
+    if !valid_grid_size(c.grid_size) {
+        return Err(NUError::MiscError(format!("bad grid size {}", c.grid_size)));
+    }
+
+    let mut surfaces = vec![];
+    for s in &c.surfaces {
+        let ([a0, a1, a2, b0, b1, b2, c0, c1, c2], [n0, n1, n2]) = (&s.verts[..], &s.normal[..])
+        else {
+            return Err(NUError::MiscError("malformed surface".to_string()));
+        };
+        let verts = [
+            mcap::Vec3::new(*a0, *a1, *a2),
+            mcap::Vec3::new(*b0, *b1, *b2),
+            mcap::Vec3::new(*c0, *c1, *c2),
+        ];
+        let normal = mcap::Vec3::new(*n0, *n1, *n2);
+        let material = materials
+            .get(s.material as usize)
+            .map(surface_material)
+            .unwrap_or_default();
+
+        let t = mcap::Triangle::new(verts, normal, material);
+        surfaces.push(match s.kind {
+            0 => mcap::Surface::Wall(t),
+            1 => mcap::Surface::Floor(t),
+            2 => mcap::Surface::Slide(t),
+            3 => mcap::Surface::Cieling(t),
+            k => return Err(NUError::MiscError(format!("unknown surface kind {k}"))),
+        });
+    }
+
+    let mut cells = vec![];
+    for cell in &c.cells {
+        let [x, y, z] = cell.cell[..] else {
+            return Err(NUError::MiscError("malformed cell".to_string()));
+        };
+        cells.push(((x, y, z), cell.surfaces.clone()));
+    }
+
+    Ok(BakedCollision {
+        instances: c.instances.iter().map(|i| *i as usize).collect(),
+        surfaces,
+        cells,
+    })
+}
+
+fn parse_vec3(value: &str) -> Option<Vector3> {
+    let parts: Vec<Option<f32>> = value.split(',').map(|p| p.trim().parse().ok()).collect();
+    match parts[..] {
+        [Some(x), Some(y), Some(z)] => Some(Vector3::new(x, y, z)),
+        _ => None,
+    }
+}
+
+pub fn load(name: &str, payload: Payload) -> Result<Map, NUError> {
     eprintln!("entts: {:?}", payload.ern_data);
     eprintln!("keyvs: {:?}", payload.kvs_data);
     eprintln!("frams: {:?}", payload.fn_data);
@@ -136,7 +386,7 @@ pub fn load(payload: Payload) -> Result<Map, NUError> {
         // mesh collisions not allowed for entities,
         // as they may be animated
         let mut mesh_verts = vec![];
-        if re.is_decor {
+        if re.is_decor && !re.no_collide {
             let n_verts = verts[0].len() / 3;
             for i in 0..n_verts {
                 let v1: Vector3 = verts[0][i * 3 + 0].into();
@@ -147,6 +397,25 @@ pub fn load(payload: Payload) -> Result<Map, NUError> {
             }
         }
 
+        let mut materials = vec![];
+        if !mesh_verts.is_empty() {
+            if re.materials.len() == mesh_verts.len() {
+                for m in &re.materials {
+                    match payload.materials.get(*m as usize) {
+                        Some(m) => materials.push(surface_material(m)),
+                        None => materials.push(mcap::Material::default()),
+                    }
+                }
+            } else {
+                eprintln!(
+                    "W: {} has {} materials for {} triangles, using defaults",
+                    level_payload.ern_data[re.name as usize],
+                    re.materials.len(),
+                    mesh_verts.len()
+                );
+            }
+        }
+
         let mut frame_names = vec![];
         for name in &re.frame_names {
             frame_names.push(level_payload.fn_data[*name as usize].clone());
@@ -159,13 +428,38 @@ pub fn load(payload: Payload) -> Result<Map, NUError> {
             frame_handles: pack_floats(verts, uvs)?,
             num_verts: vlen,
             mesh: mesh_verts,
+            materials,
+            is_decor: re.is_decor,
+            no_collide: re.no_collide,
+            no_draw: re.no_draw,
         })
     }
 
+    let grid_size = match payload.collision.grid_size {
+        s if valid_grid_size(s) => s,
+        s => {
+            eprintln!(
+                "W: {name} has a grid size of {s}, using {}",
+                mcap::GRID_SIZE
+            );
+            mcap::GRID_SIZE
+        }
+    };
+
+    // rebuilt by g_game::init_level if it's unusable
+    let collision = match load_collision(&payload.collision, &payload.materials) {
+        Ok(c) => Some(c),
+        Err(e) => {
+            eprintln!("W: ignoring {name}'s baked collision, {e}");
+            None
+        }
+    };
+
     // map entities
     let mut map_entts = vec![];
-    for ei in &payload.map_ins_ents {
+    for (index, ei) in payload.map_ins_ents.iter().enumerate() {
         let entity = Entity {
+            index,
             ref_id: ei.index as usize,
             has_ref: ei.has_ref,
             params: ei.params.clone(),
@@ -190,11 +484,14 @@ pub fn load(payload: Payload) -> Result<Map, NUError> {
     }
 
     Ok(Map {
+        name: name.to_string(),
         payload: level_payload,
         // needs to eat copies of payloads _data fields
         // which need to have corresponding lookup functions
         // in this file (level.rs)
         map_entities: map_entts,
         ref_entities: ref_entts,
+        grid_size,
+        collision,
     })
 }
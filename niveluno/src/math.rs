@@ -1,5 +1,5 @@
 pub use raymath::*;
-use mcap::Vec3;
+use mcap::{Affine3A, Surface, Vec3};
 
 
 pub trait ToVec3 {
@@ -44,6 +44,31 @@ pub fn vector3_transform(v: Vector3, mat: Matrix) -> Vector3 {
     }
 }
 
+/// Converts a Matrix to an mcap affine transform, dropping the projective row
+pub fn matrix_to_affine(mat: Matrix) -> Affine3A {
+    Affine3A::from_cols(
+        Vec3::new(mat.m0, mat.m1, mat.m2),
+        Vec3::new(mat.m4, mat.m5, mat.m6),
+        Vec3::new(mat.m8, mat.m9, mat.m10),
+        Vec3::new(mat.m12, mat.m13, mat.m14),
+    )
+}
+
+/// Classifies each triangle of a mesh as an mcap Surface,
+/// triangles past the end of materials get the default
+pub fn mesh_surfaces(mesh: &[[Vector3; 3]], materials: &[mcap::Material]) -> Vec<Surface> {
+    mesh.iter()
+        .enumerate()
+        .map(|(i, tri)| {
+            Surface::new_with_material(
+                [tri[0].to_mcapv3(), tri[1].to_mcapv3(), tri[2].to_mcapv3()],
+                vec3_face_normal(tri[0], tri[1], tri[2]).to_mcapv3(),
+                materials.get(i).copied().unwrap_or_default(),
+            )
+        })
+        .collect()
+}
+
 /// Transforms a Vec<[Vector3;3]> by a given Matrix
 pub fn mesh_tranform(mesh: Vec<[Vector3; 3]>, mat: Matrix) -> Vec<[Vector3; 3]> {
     let mut out = vec![];
@@ -34,6 +34,8 @@ pub enum NUError {
     SDLError(String),
     #[error("MiniPNG error: {0}")]
     MiniPNGError(String),
+    #[error("Script error: {0}")]
+    ScriptError(String),
     #[error("Miscellaneous error: {0}")]
     MiscError(String),
 }
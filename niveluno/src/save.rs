@@ -0,0 +1,70 @@
+use msgpacker::prelude::*;
+
+use crate::nuerror::NUError;
+
+// bump whenever SaveGame or anything inside it changes shape,
+// old saves are refused rather than misread
+pub const SAVE_VERSION: u32 = 2;
+
+const SAVE_FILE: &str = "niveluno.sav";
+
+#[derive(Debug, MsgPacker, Clone, PartialEq)]
+pub struct PlayerState {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub coins: u32,
+}
+
+// where the player respawns, see g_game::set_checkpoint
+#[derive(Debug, MsgPacker, Clone, PartialEq)]
+pub struct CheckpointState {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub yaw: f32,
+}
+
+#[derive(Debug, MsgPacker, Clone, PartialEq)]
+pub struct SaveGame {
+    pub level: String,
+    pub player: PlayerState,
+    // Entity::index of every map entity that has died,
+    // e.g. collected coins, these aren't spawned on load
+    pub removed: Vec<u32>,
+    // None until a checkpoint is passed, quicksaves aren't one
+    pub checkpoint: Option<CheckpointState>,
+}
+
+pub fn write(save: &SaveGame) -> Result<(), NUError> {
+    let mut buf = vec![];
+    SAVE_VERSION.pack(&mut buf);
+    save.pack(&mut buf);
+
+    std::fs::write(SAVE_FILE, buf)?;
+    eprintln!("saved '{}'", SAVE_FILE);
+
+    Ok(())
+}
+
+pub fn read() -> Result<SaveGame, NUError> {
+    let buf = std::fs::read(SAVE_FILE)?;
+
+    let (n, version) = u32::unpack(&buf)
+        .map_err(|_| NUError::MiscError(format!("'{SAVE_FILE}' has no version")))?;
+
+    if version != SAVE_VERSION {
+        return Err(NUError::MiscError(format!(
+            "'{SAVE_FILE}' is version {version}, expected {SAVE_VERSION}"
+        )));
+    }
+
+    let (_, save) = SaveGame::unpack(&buf[n..])
+        .map_err(|_| NUError::MiscError(format!("failed to read '{SAVE_FILE}'")))?;
+
+    eprintln!("loaded '{}'", SAVE_FILE);
+
+    Ok(save)
+}
@@ -0,0 +1,196 @@
+// rhai scripting for map entities, see e_script.rs
+//
+// bindings, floats are f64 and ints i64 on the script side:
+//   time::delta(), time::run_time()
+//   input::held(key), input::pressed(key)
+//     key is one of up down left right prev next action jump sprint
+//   render::light(x, y, z, intensity, r, g, b)
+//   render::debug_point(x, y, z), render::debug_line(x1, y1, z1, x2, y2, z2)
+//   text::notice(str)
+//   game::player_position(), a map #{x, y, z} or () without a player
+//   game::activate(target), game::change_level(map)
+
+use rhai::{Dynamic, Engine, EvalAltResult, Map, Module, AST};
+
+use crate::g_game::{self, Event};
+use crate::nuerror::NUError;
+use crate::{asset, g_instance, input, render, time};
+use raymath::Vector3;
+
+struct ScriptGod {
+    pub engine: Engine,
+}
+
+impl ScriptGod {
+    pub fn get() -> Result<&'static mut ScriptGod, NUError> {
+        unsafe {
+            SCRIPT_GOD
+                .as_mut()
+                .ok_or_else(|| NUError::MiscError("SCRIPT_GOD uninit".to_string()))
+        }
+    }
+}
+
+static mut SCRIPT_GOD: Option<ScriptGod> = None;
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+// surfaces engine errors as script errors rather than panicking in the script
+fn script_err<T>(r: Result<T, NUError>) -> ScriptResult<T> {
+    r.map_err(|e| e.to_string().into())
+}
+
+fn key_from_str(key: &str) -> ScriptResult<input::Key> {
+    Ok(match key {
+        "up" => input::Key::Up,
+        "down" => input::Key::Down,
+        "left" => input::Key::Left,
+        "right" => input::Key::Right,
+        "prev" => input::Key::Prev,
+        "next" => input::Key::Next,
+        "action" => input::Key::Action,
+        "jump" => input::Key::Jump,
+        "sprint" => input::Key::Sprint,
+        _ => return Err(format!("unknown key '{key}'").into()),
+    })
+}
+
+fn time_module() -> Module {
+    let mut m = Module::new();
+    m.set_native_fn("delta", || script_err(time::get_delta_time()));
+    m.set_native_fn("run_time", || script_err(time::get_run_time()));
+    m
+}
+
+fn input_module() -> Module {
+    let mut m = Module::new();
+    m.set_native_fn("held", |key: &str| {
+        Ok(script_err(input::get_keys())?[key_from_str(key)? as usize])
+    });
+    m.set_native_fn("pressed", |key: &str| {
+        Ok(script_err(input::get_pressed_keys())?[key_from_str(key)? as usize])
+    });
+    m
+}
+
+fn render_module() -> Module {
+    let mut m = Module::new();
+    m.set_native_fn(
+        "light",
+        |x: f64, y: f64, z: f64, intensity: i64, r: i64, g: i64, b: i64| {
+            let pos = Vector3::new(x as f32, y as f32, z as f32);
+            let c = |v: i64| v.clamp(0, 255) as u8;
+            script_err(render::push_light(pos, c(intensity), c(r), c(g), c(b)))
+        },
+    );
+    m.set_native_fn("debug_point", |x: f64, y: f64, z: f64| {
+        let pos = Vector3::new(x as f32, y as f32, z as f32);
+        script_err(render::push_debug_point(pos, 1., 1., 0., 1.))
+    });
+    m.set_native_fn(
+        "debug_line",
+        |x1: f64, y1: f64, z1: f64, x2: f64, y2: f64, z2: f64| {
+            let v1 = Vector3::new(x1 as f32, y1 as f32, z1 as f32);
+            let v2 = Vector3::new(x2 as f32, y2 as f32, z2 as f32);
+            script_err(render::push_debug_line(v1, v2, 1., 1., 0., 1.))
+        },
+    );
+    m
+}
+
+fn text_module() -> Module {
+    let mut m = Module::new();
+    m.set_native_fn("notice", |s: &str| script_err(g_game::push_notice(s)));
+    m
+}
+
+fn game_module() -> Module {
+    let mut m = Module::new();
+    m.set_native_fn("player_position", || {
+        let Ok(player) = g_instance::with_player(|player| player.position) else {
+            return Ok(Dynamic::UNIT);
+        };
+        let mut pos = Map::new();
+        pos.insert("x".into(), (player.x as f64).into());
+        pos.insert("y".into(), (player.y as f64).into());
+        pos.insert("z".into(), (player.z as f64).into());
+        Ok(Dynamic::from_map(pos))
+    });
+    m.set_native_fn("activate", |target: &str| {
+        script_err(g_game::emit(Event::Triggered {
+            target: target.to_string(),
+        }))
+    });
+    m.set_native_fn("change_level", |map: &str| {
+        script_err(g_game::emit(Event::LevelChange {
+            map: map.to_string(),
+            spawn: None,
+        }))
+    });
+    m
+}
+
+pub fn init() -> Result<(), NUError> {
+    if ScriptGod::get().is_ok() {
+        return Err(NUError::MiscError("SCRIPT_GOD already init".to_string()));
+    }
+
+    let mut engine = Engine::new();
+    // a runaway loop in a script shouldn't hang the game
+    engine.set_max_operations(100_000);
+
+    engine.register_static_module("time", time_module().into());
+    engine.register_static_module("input", input_module().into());
+    engine.register_static_module("render", render_module().into());
+    engine.register_static_module("text", text_module().into());
+    engine.register_static_module("game", game_module().into());
+
+    unsafe { SCRIPT_GOD = Some(ScriptGod { engine }) }
+
+    Ok(())
+}
+
+// loads and compiles a script from the asset archive
+pub fn load(path: &str) -> Result<AST, NUError> {
+    let sg = ScriptGod::get()?;
+
+    let bytes = asset::get_file(path)?
+        .ok_or_else(|| NUError::ScriptError(format!("'{path}' not found")))?;
+    let source = String::from_utf8(bytes)?;
+
+    sg.engine
+        .compile(source)
+        .map_err(|e| NUError::ScriptError(format!("'{path}': {e}")))
+}
+
+pub fn has_fn(ast: &AST, name: &str) -> bool {
+    ast.iter_functions().any(|f| f.name == name)
+}
+
+// runs the script's top level statements, once, into scope
+pub fn run(ast: &AST, scope: &mut rhai::Scope<'static>) -> Result<(), NUError> {
+    let sg = ScriptGod::get()?;
+
+    sg.engine
+        .run_ast_with_scope(scope, ast)
+        .map_err(|e| NUError::ScriptError(e.to_string()))
+}
+
+// calls fn name() in the script with this bound to this
+pub fn call(
+    ast: &AST,
+    scope: &mut rhai::Scope<'static>,
+    this: &mut Dynamic,
+    name: &str,
+) -> Result<(), NUError> {
+    let sg = ScriptGod::get()?;
+
+    let options = rhai::CallFnOptions::new()
+        .eval_ast(false)
+        .bind_this_ptr(this);
+
+    sg.engine
+        .call_fn_with_options::<Dynamic>(options, scope, ast, name, ())
+        .map(|_| ())
+        .map_err(|e| NUError::ScriptError(format!("{name}(): {e}")))
+}
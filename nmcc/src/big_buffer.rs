@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 
+use mparse::exports::SurfaceMaterial;
+
 #[derive(Debug)]
 pub enum HashItem {
     Uv__([f32; 2]),
@@ -57,6 +59,9 @@ pub struct BigBuffer {
     // parameter kvs
     kvs_data: Vec<String>,
     kvs_hmap: HashMap<String, u32>,
+    // surface materials, keyed by glTF material index
+    mat_data: Vec<SurfaceMaterial>,
+    mat_hmap: HashMap<Option<usize>, u32>,
 }
 
 impl BigBuffer {
@@ -71,6 +76,8 @@ impl BigBuffer {
             ern_hmap: HashMap::new(),
             kvs_data: Vec::new(),
             kvs_hmap: HashMap::new(),
+            mat_data: Vec::new(),
+            mat_hmap: HashMap::new(),
         };
 
         // 3 uvs, 2 vers, 1 quat
@@ -260,6 +267,22 @@ impl BigBuffer {
         len as u32
     }
 
+    pub fn get_material_index(&self, gltf_index: Option<usize>) -> Option<u32> {
+        self.mat_hmap.get(&gltf_index).copied()
+    }
+
+    pub fn add_material(&mut self, gltf_index: Option<usize>, m: SurfaceMaterial) -> u32 {
+        if let Some(&index) = self.mat_hmap.get(&gltf_index) {
+            return index;
+        }
+
+        let len = self.mat_data.len();
+        self.mat_hmap.insert(gltf_index, len as u32);
+        self.mat_data.push(m);
+
+        len as u32
+    }
+
     pub fn get_vert_at(&self, i: usize) -> Option<[f32; 3]> {
         if self.f32_data.len() < i + 3 {
             return None;
@@ -290,4 +313,8 @@ impl BigBuffer {
     pub fn get_kvs_data(&self) -> &Vec<String> {
         &self.kvs_data
     }
+
+    pub fn get_mat_data(&self) -> &Vec<SurfaceMaterial> {
+        &self.mat_data
+    }
 }
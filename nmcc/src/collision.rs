@@ -0,0 +1,247 @@
+use mcap::{Surface, SurfaceGrid};
+use mparse::exports::*;
+use raymath::{Matrix, Vector3};
+
+// how niveluno's decor place their reference meshes,
+// scale, rotation, translation, see d_floor.rs
+fn instance_matrix(ins: &EntityInstance, floats: &[f32]) -> Matrix {
+    let location = vec3_at(floats, ins.location);
+    let scale = vec3_at(floats, ins.scale);
+    let r = ins.rotation as usize;
+    let rotation = [floats[r], floats[r + 1], floats[r + 2], floats[r + 3]];
+
+    let mat_s = raymath::matrix_scale(scale.x, scale.y, scale.z);
+    let mat_r = raymath::quaternion_to_matrix(rotation.into());
+    let mat_t = raymath::matrix_translate(location.x, location.y, location.z);
+
+    let mut matrix = raymath::matrix_identity();
+    matrix = raymath::matrix_multiply(matrix, mat_s);
+    matrix = raymath::matrix_multiply(matrix, mat_r);
+    raymath::matrix_multiply(matrix, mat_t)
+}
+
+// niveluno's d_platform only places its mesh by location
+fn platform_matrix(ins: &EntityInstance, floats: &[f32]) -> Matrix {
+    let location = vec3_at(floats, ins.location);
+    raymath::matrix_translate(location.x, location.y, location.z)
+}
+
+fn param<'a>(ins: &EntityInstance, kvs: &'a [String], key: &str) -> Option<&'a str> {
+    ins.params
+        .chunks_exact(2)
+        .find(|kv| kvs.get(kv[0] as usize).is_some_and(|k| k == key))
+        .and_then(|kv| kvs.get(kv[1] as usize))
+        .map(String::as_str)
+}
+
+// platforms with a path or a spin are bodies at runtime, not grid surfaces,
+// a bad value means niveluno won't spawn it, so neither counts
+fn is_moving_platform(ins: &EntityInstance, kvs: &[String]) -> bool {
+    let spin = param(ins, kvs, "spin").map(|v| v.trim().parse::<f32>());
+    param(ins, kvs, "path").is_some() || spin.is_some_and(|s| s != Ok(0.))
+}
+
+fn vec3_at(floats: &[f32], i: u32) -> Vector3 {
+    let i = i as usize;
+    [floats[i], floats[i + 1], floats[i + 2]].into()
+}
+
+// same winding as niveluno's math::vec3_face_normal
+fn face_normal(v0: Vector3, v1: Vector3, v2: Vector3) -> Vector3 {
+    let lh = raymath::vector3_subtract(v0, v1);
+    let rh = raymath::vector3_subtract(v2, v1);
+    raymath::vector3_normalize(raymath::vector3_cross_product(lh, rh))
+}
+
+fn to_mcap(v: Vector3) -> mcap::Vec3 {
+    mcap::Vec3::new(v.x, v.y, v.z)
+}
+
+// only the slide angle changes how a surface is classified,
+// converted like niveluno's map::surface_material
+fn classifying_material(m: Option<&SurfaceMaterial>) -> mcap::Material {
+    let slide_angle = m.map_or(0., |m| m.slide_angle);
+    mcap::Material {
+        slide_angle: (slide_angle > 0.).then(|| slide_angle.to_radians()),
+        ..Default::default()
+    }
+}
+
+// every collidable decor instance's triangles in world space, bucketed
+// the way niveluno would at load, so it can skip straight to the grid
+pub fn bake(
+    refs: &[EntityReference],
+    instances: &[EntityInstance],
+    ern: &[String],
+    kvs: &[String],
+    floats: &[f32],
+    materials: &[SurfaceMaterial],
+    grid_size: f32,
+) -> Collision {
+    let mut baked = vec![];
+    let mut surfaces = vec![];
+    let mut surface_materials = vec![];
+
+    for (i, ins) in instances.iter().enumerate() {
+        if !ins.has_ref {
+            continue;
+        }
+        let Some(r) = refs.get(ins.index as usize) else {
+            continue;
+        };
+        let Some(frame) = r.vertices.first().filter(|f| !f.is_empty()) else {
+            continue;
+        };
+        // entities may be animated, they never collide by mesh
+        if !r.is_decor || r.no_collide {
+            continue;
+        }
+
+        let is_platform = ern.get(r.name as usize).is_some_and(|n| n == "platform");
+        if is_platform && is_moving_platform(ins, kvs) {
+            continue;
+        }
+
+        let matrix = match is_platform {
+            true => platform_matrix(ins, floats),
+            false => instance_matrix(ins, floats),
+        };
+        for (t, tri) in frame.chunks_exact(3).enumerate() {
+            let [v0, v1, v2] = [tri[0], tri[1], tri[2]]
+                .map(|v| raymath::vector3_transform(vec3_at(floats, v), matrix));
+            // out of range is the default material, like a mismatched count at load
+            let material = match r.materials.len() == frame.len() / 3 {
+                true => r.materials[t],
+                false => u32::MAX,
+            };
+
+            surfaces.push(Surface::new_with_material(
+                [to_mcap(v0), to_mcap(v1), to_mcap(v2)],
+                to_mcap(face_normal(v0, v1, v2)),
+                classifying_material(materials.get(material as usize)),
+            ));
+            surface_materials.push(material);
+        }
+        baked.push(i as u32);
+    }
+
+    let grid = SurfaceGrid::with_cell_size(surfaces, grid_size);
+    let (surfaces, cells) = grid.cell_indices();
+
+    Collision {
+        grid_size: grid.cell_size(),
+        instances: baked,
+        surfaces: surfaces
+            .iter()
+            .zip(surface_materials)
+            .map(|(s, material)| {
+                let t = s.triangle();
+                CollisionSurface {
+                    verts: t.verts().iter().flat_map(|v| v.to_array()).collect(),
+                    normal: t.normal().to_array().to_vec(),
+                    kind: match s {
+                        Surface::Wall(_) => 0,
+                        Surface::Floor(_) => 1,
+                        Surface::Slide(_) => 2,
+                        Surface::Cieling(_) => 3,
+                    },
+                    material,
+                }
+            })
+            .collect(),
+        cells: cells
+            .into_iter()
+            .map(|((x, y, z), surfaces)| CollisionCell {
+                cell: vec![x, y, z],
+                surfaces,
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a 2x2 floor quad at the origin
+    const FLOATS: &[f32] = &[
+        -1., 0., -1., -1., 0., 1., 1., 0., 1., 1., 0., -1., // corners
+        0., 0., 0., // 12, location
+        0., 5., 40., // 15, location
+        0., 0., 0., 1., // 18, rotation
+        1., 1., 1., // 22, scale
+    ];
+
+    fn reference(name: u32) -> EntityReference {
+        EntityReference {
+            name,
+            is_decor: true,
+            no_collide: false,
+            no_draw: false,
+            frame_names: vec![],
+            texture: 0,
+            vertices: vec![vec![0, 6, 3, 0, 9, 6]],
+            uvs: vec![],
+            materials: vec![],
+        }
+    }
+
+    fn instance(index: u32, location: u32, params: Vec<u32>) -> EntityInstance {
+        EntityInstance {
+            index,
+            has_ref: true,
+            params,
+            location,
+            rotation: 18,
+            scale: 22,
+        }
+    }
+
+    #[test]
+    fn moving_platforms_are_left_out() {
+        let ern = ["floor", "platform"].map(String::from);
+        let kvs = ["path", "0,0,4", "spin", "90", "0", "speed", "2"].map(String::from);
+        let refs = [reference(0), reference(1)];
+        let instances = [
+            instance(0, 12, vec![]),
+            instance(1, 15, vec![]),
+            instance(1, 15, vec![0, 1]),
+            instance(1, 15, vec![2, 3]),
+            instance(1, 15, vec![2, 4]),
+            instance(1, 15, vec![5, 6]),
+        ];
+
+        let c = bake(&refs, &instances, &ern, &kvs, FLOATS, &[], 8.);
+
+        // what niveluno's init_level collects, so the bake is used
+        assert_eq!(c.instances, vec![0, 1, 4, 5]);
+        assert_eq!(c.surfaces.len(), 8);
+        assert!(c.surfaces.iter().all(|s| s.kind == 1));
+        assert!(c
+            .cells
+            .iter()
+            .flat_map(|c| &c.surfaces)
+            .all(|i| (*i as usize) < c.surfaces.len()));
+
+        // placed platforms only move, the floor isn't
+        let heights: Vec<f32> = c.surfaces.iter().map(|s| s.verts[1]).collect();
+        assert_eq!(heights.iter().filter(|y| **y == 5.).count(), 6);
+
+        // and it loads without a rebuild
+        let surfaces = c
+            .surfaces
+            .iter()
+            .map(|s| {
+                let v: Vec<mcap::Vec3> = s.verts.chunks(3).map(mcap::Vec3::from_slice).collect();
+                Surface::new([v[0], v[1], v[2]], mcap::Vec3::from_slice(&s.normal))
+            })
+            .collect();
+        let cells = c
+            .cells
+            .into_iter()
+            .map(|c| ((c.cell[0], c.cell[1], c.cell[2]), c.surfaces))
+            .collect();
+        let grid = SurfaceGrid::from_cell_indices(surfaces, cells, c.grid_size).unwrap();
+        assert_eq!(grid.surfaces_near(mcap::Vec3::new(0., 5.1, 40.)).len(), 6);
+    }
+}
@@ -11,6 +11,7 @@ use serde::Deserialize;
 use serde_json;
 
 mod big_buffer;
+mod collision;
 
 #[derive(Debug)]
 struct Vec3 {
@@ -91,6 +92,8 @@ fn get_reference(
         _noref: Option<&'a str>,
         _entity: Option<&'a str>,
         _decor: Option<&'a str>,
+        _nocollide: Option<&'a str>,
+        _nodraw: Option<&'a str>,
     }
 
     let extras: Option<Extras>;
@@ -116,8 +119,17 @@ fn get_reference(
         Some(Extras {
             _type: Some("decor"),
             _decor: Some(name),
+            _nocollide,
+            _nodraw,
             ..
-        }) => parse_ref_decor(n, b, bb, name, image_cache),
+        }) => {
+            let no_collide = _nocollide == Some("true");
+            let no_draw = _nodraw == Some("true");
+            if no_collide && no_draw {
+                eprintln!("W: decor {:?} is neither drawn nor collidable", n.name());
+            }
+            parse_ref_decor(n, b, bb, name, no_collide, no_draw, image_cache)
+        }
         Some(Extras {
             _type: Some("entity"),
             _entity: Some(name),
@@ -384,11 +396,82 @@ fn image_from_prim(
     result
 }
 
+// a primitive's glTF material as a surface material, flags come from
+// the material's extras or its name, e.g. "ice" or "mud.001"
+fn material_from_prim(prim: &gltf::Primitive, bb: &mut big_buffer::BigBuffer) -> u32 {
+    let mat = prim.material();
+    if let Some(index) = bb.get_material_index(mat.index()) {
+        return index;
+    }
+
+    let name = mat.name().unwrap_or("default");
+    // blender suffixes duplicates
+    let base = name.split('.').next().unwrap_or(name);
+
+    let kvp = match mat.extras() {
+        Some(e) => json_string_pairs(e.get()),
+        None => vec![],
+    };
+    let get = |key: &str| kvp.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str());
+    let flag = |key: &str| get(&format!("_{key}")) == Some("true") || base == key;
+    let float = |key: &str, default: f32| match get(key) {
+        None => default,
+        Some(v) => v
+            .parse()
+            .ok()
+            .filter(|f: &f32| *f >= 0.)
+            .unwrap_or_else(|| {
+                eprintln!("W: material {name:?} has bad {key} '{v}'");
+                default
+            }),
+    };
+
+    let m = SurfaceMaterial {
+        name: bb.add_kv_string(name),
+        friction: float("_friction", 1.),
+        slide_angle: float("_slide_angle", 0.),
+        ice: flag("ice"),
+        mud: flag("mud"),
+        bouncy: flag("bouncy"),
+        // footsteps sound like the material unless told otherwise
+        sound: bb.add_kv_string(get("_sound").unwrap_or(base)),
+    };
+    bb.add_material(mat.index(), m)
+}
+
+// the collision cell size, from the scene's extras, bigger suits
+// open maps and smaller suits dense ones, see mcap's SurfaceGrid
+fn scene_grid_size(document: &gltf::Document) -> f32 {
+    let scene = document
+        .default_scene()
+        .or_else(|| document.scenes().next());
+    let kvp = match scene.as_ref().and_then(|s| s.extras().as_ref()) {
+        Some(e) => json_string_pairs(e.get()),
+        None => vec![],
+    };
+    let Some((_, v)) = kvp.iter().find(|(k, _)| k == "_grid_size") else {
+        return mcap::GRID_SIZE;
+    };
+
+    match v.parse::<f32>() {
+        Ok(size) if size.is_finite() && size >= mcap::MIN_GRID_SIZE => size,
+        _ => {
+            eprintln!(
+                "W: scene has bad _grid_size '{v}', it must be at least {}",
+                mcap::MIN_GRID_SIZE
+            );
+            mcap::GRID_SIZE
+        }
+    }
+}
+
 fn parse_ref_decor(
     n: &gltf::Node,
     b: &Vec<gltf::buffer::Data>,
     bb: &mut big_buffer::BigBuffer,
     name: &str,
+    no_collide: bool,
+    no_draw: bool,
     image_cache: &mut ImageCache,
 ) -> Option<EntityReference> {
     let mesh = n.mesh().or_else(|| {
@@ -405,6 +488,7 @@ fn parse_ref_decor(
     let mut out_pos = vec![];
     let mut out_uvs = vec![];
     let mut out_img = vec![];
+    let mut out_mats = vec![];
 
     for i in 0..primitives.len() {
         let prim = primitives.nth(0).or_else(|| {
@@ -459,6 +543,9 @@ fn parse_ref_decor(
             out_pos.push(index);
         }
 
+        let material = material_from_prim(&prim, bb);
+        out_mats.extend(vec![material; indices.len() / 3]);
+
         // push uvs to floatbuffer,
         // store indicies
         for i in 0..indices.len() {
@@ -497,10 +584,13 @@ fn parse_ref_decor(
     Some(EntityReference {
         name: name_id,
         is_decor: true,
+        no_collide,
+        no_draw,
         frame_names: vec![],
         vertices: vec![out_pos],
         uvs: out_uvs,
         texture: bb.add_image(out_img),
+        materials: out_mats,
     })
 }
 
@@ -670,10 +760,13 @@ fn parse_ref_entt(
     Some(EntityReference {
         name: name_id,
         is_decor: false,
+        no_collide: false,
+        no_draw: false,
         frame_names: out_frame_ids,
         vertices: out_pos,
         uvs: out_uvs,
         texture: bb.add_image(out_img),
+        materials: vec![],
     })
 }
 
@@ -743,9 +836,20 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let ern_data = bb.get_ern_data();
     let kvs_data = bb.get_kvs_data();
     let fn_data = bb.get_fn_data();
+    let mat_data = bb.get_mat_data();
+    let grid_size = scene_grid_size(&document);
+    let collision = collision::bake(
+        &map_ref_entt,
+        &map_ins_entt,
+        ern_data,
+        kvs_data,
+        f32_data,
+        mat_data,
+        grid_size,
+    );
 
     let buf = mparse::marshal(
-        0,
+        mparse::FORMAT_VERSION,
         f32_data,
         img_data,
         ern_data,
@@ -753,6 +857,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         fn_data,
         &map_ref_entt,
         &map_ins_entt,
+        mat_data,
+        &collision,
     )?;
 
     if cfg!(debug_assertions) {
@@ -764,6 +870,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         assert_eq!(&payload.ern_data, ern_data);
         assert_eq!(&payload.kvs_data, kvs_data);
         assert_eq!(&payload.fn_data, fn_data);
+        assert_eq!(&payload.materials, mat_data);
+        assert_eq!(&payload.collision, &collision);
         // assert_eq!(&payload.map_ref_ents, &map_ref_entt);
         // assert_eq!(&payload.map_ins_ents, &map_ins_entt);
     }